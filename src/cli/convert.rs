@@ -0,0 +1,477 @@
+use anyhow::Result;
+use bitcoin::{Address, AddressType, Network, PrivateKey, PublicKey, ScriptBuf};
+use btc_address_tools::converter::{self, AddressEncoding, AddressKind, ConversionReport, KeyEncoding, TaprootTweak};
+use btc_address_tools::script::{self, NoAddress};
+use btc_address_tools::utils;
+use colored::*;
+
+use super::output::{self, ConvertOutput, ExampleOutput, InspectOutput, OutputFormat, ReencodeOutput, ScriptToAddressOutput};
+
+/// Convert between different Bitcoin address formats
+///
+/// `verbose` additionally shows how the Taproot output key was derived;
+/// private keys are only printed back with `show_private`.
+pub fn run(input: &str, testnet: bool, verbose: bool, show_private: bool, format: OutputFormat) -> Result<()> {
+    let report = converter::convert_formats(input, testnet)?;
+    if matches!(report, ConversionReport::PrivateKey { .. }) {
+        eprintln!("{} A private key on the command line stays in shell history and is visible in the process list",
+                  "⚠️  Warning:".bright_yellow().bold());
+    }
+    if !format.is_text() {
+        return output::emit(format, &ConvertOutput::new(&report, show_private)?);
+    }
+
+    let network = match &report {
+        ConversionReport::PublicKey { network, .. }
+        | ConversionReport::PrivateKey { network, .. }
+        | ConversionReport::Address { network, .. } => *network,
+    };
+
+    println!("\n{}", "━".repeat(70).bright_blue());
+    println!("{}", "  🔄 ADDRESS FORMAT CONVERTER".bright_cyan().bold());
+    println!("  {} {}", "Network:".bold(), utils::network_name(network).bright_yellow());
+    println!("{}", "━".repeat(70).bright_blue());
+
+    match report {
+        ConversionReport::PublicKey { network, public_key, formats } => {
+            display_all_formats_from_pubkey(&public_key, &formats, network);
+            display_compressed_equivalent(&public_key, &formats, network)?;
+            display_key_footer(&public_key, network, verbose);
+        }
+        ConversionReport::PrivateKey { network, private_key, encoding, public_key, formats } => {
+            display_private_key(&private_key, encoding, show_private);
+            display_all_formats_from_pubkey(&public_key, &formats, network);
+            display_compressed_equivalent(&public_key, &formats, network)?;
+            display_key_footer(&public_key, network, verbose);
+        }
+        ConversionReport::Address { address, .. } => {
+            println!("\n  {} Input address detected: {}", "ℹ".bright_blue(), address.to_string().bright_white());
+            println!("  {} Address type: {}", "ℹ".bright_blue(),
+                     format!("{:?}", address.address_type()).bright_yellow());
+
+            // Note: We can't extract the public key from an address alone
+            // We can only show what formats would exist IF we had the pubkey
+            println!("\n  {} Cannot generate other formats from address alone", "⚠".yellow());
+            println!("  {} Addresses are one-way hashes of public keys", "ℹ".bright_blue());
+            println!("  {} To convert formats, please provide the public key (hex)", "💡".bright_yellow());
+            println!("  {} To decode its fields and script, run: btc-tools inspect {}", "🔬".bright_yellow(), address);
+
+            display_address_info(&address);
+        }
+    }
+
+    Ok(())
+}
+
+/// Why an uncompressed key only has a P2PKH address, with the addresses of
+/// its compressed form alongside
+fn display_compressed_equivalent(pubkey: &PublicKey, formats: &[Address], network: Network) -> Result<()> {
+    let Some(equivalent) = converter::compressed_equivalent(pubkey, network)? else {
+        return Ok(());
+    };
+
+    println!("\n{}", "  ⚠️  UNCOMPRESSED PUBLIC KEY:".bright_yellow().bold());
+    println!("{}", "  ━".repeat(35).cyan());
+    println!("  {} SegWit only accepts compressed keys: since BIP143, spending P2WPKH or", "•".yellow());
+    println!("    P2WSH with an uncompressed key is non-standard and will not be relayed,");
+    println!("    so coins sent to such an address could be stuck");
+    println!("  {} Taproot keys are 32-byte x-only (BIP340), with no uncompressed form", "•".yellow());
+    println!("  {} Both forms are the same point and private key, but they hash to", "•".yellow());
+    println!("    different P2PKH addresses - wallets from before 2012 used the uncompressed one");
+
+    println!("\n  {} {}", "Compressed Key:".bold(), equivalent.public_key.to_string().bright_white());
+    println!("\n  {:<14} {:<14} {}", "Format".bold(), "Key Form".bold(), "Address".bold());
+    println!("  {}", "─".repeat(66).dimmed());
+    for compressed_address in &equivalent.formats {
+        let format = match compressed_address.address_type() {
+            Some(AddressType::P2pkh) => "P2PKH",
+            Some(AddressType::P2sh) => "P2SH-P2WPKH",
+            Some(AddressType::P2wpkh) => "P2WPKH",
+            Some(AddressType::P2tr) => "P2TR",
+            _ => "other",
+        };
+        let uncompressed = formats.iter().find(|address| address.address_type() == compressed_address.address_type());
+        let uncompressed = match uncompressed {
+            Some(address) => address.to_string().bright_green(),
+            None => "not allowed".dimmed(),
+        };
+        println!("  {:<14} {:<14} {}", format, "uncompressed", uncompressed);
+        println!("  {:<14} {:<14} {}", "", "compressed", compressed_address.to_string().bright_green());
+    }
+    println!("{}", "  ━".repeat(35).cyan());
+    Ok(())
+}
+
+/// Taproot tweak (with `verbose`) and mainnet fee comparison after a key's formats
+fn display_key_footer(public_key: &PublicKey, network: Network, verbose: bool) {
+    if !public_key.compressed {
+        // No Taproot address to explain
+    } else if verbose {
+        display_taproot_tweak(&converter::bip86_tweak(public_key));
+    } else {
+        println!("\n  {} Use --verbose to see how the Taproot output key is derived", "ℹ".bright_blue());
+    }
+    if network == Network::Bitcoin {
+        display_fee_comparison();
+    }
+}
+
+fn display_private_key(private_key: &PrivateKey, encoding: KeyEncoding, show_private: bool) {
+    println!("\n{}", "  🔐 PRIVATE KEY:".bright_cyan().bold());
+    println!("{}", "  ━".repeat(35).cyan());
+    let compression = if private_key.compressed { "compressed" } else { "uncompressed" };
+    let input = match encoding {
+        KeyEncoding::Wif => format!("WIF ({}, {})", compression, utils::network_name(private_key.network)),
+        KeyEncoding::Hex => "32-byte hex (taken as compressed)".to_string(),
+    };
+    println!("  {} {}", "Input:".bold(), input.bright_white());
+
+    for key in converter::wif_encodings(private_key) {
+        let network = if key.network == Network::Bitcoin { "mainnet" } else { "testnet/signet/regtest" };
+        let label = match (private_key.compressed, key.compressed) {
+            (false, true) => format!("Compressed WIF ({}):", network),
+            _ => format!("WIF ({}):", network),
+        };
+        if show_private {
+            println!("  {} {}", label.bold(), key.to_wif().bright_red());
+        } else {
+            println!("  {} {}", label.bold(), "hidden - pass --show-private-keys to print".dimmed());
+        }
+    }
+    if show_private {
+        println!("  {} Anyone who sees these keys can spend the funds they control", "⚠️".bright_red());
+    }
+    println!("{}", "  ━".repeat(35).cyan());
+}
+
+/// Decode an address into its encoded fields, scriptPubKey and script hash
+pub fn run_inspect(input: &str, format: OutputFormat) -> Result<()> {
+    let breakdown = converter::inspect_address(input)?;
+    if !format.is_text() {
+        return output::emit(format, &InspectOutput::from(&breakdown));
+    }
+    let info = &breakdown.info;
+
+    println!("\n{}", "━".repeat(70).bright_blue());
+    println!("{}", "  🔬 ADDRESS INSPECTOR".bright_cyan().bold());
+    println!("{}", "━".repeat(70).bright_blue());
+
+    println!("\n  {} {}", "Address:".bold(), info.address.to_string().bright_white());
+    println!("  {} {}", "Type:".bold(), info.type_name().bright_yellow());
+    println!("  {} {}", "Network:".bold(), utils::join_network_names(&info.possible_networks).bright_white());
+
+    println!("\n{}", "  🧩 ENCODING:".bright_cyan().bold());
+    println!("{}", "  ━".repeat(35).cyan());
+    match &breakdown.encoding {
+        AddressEncoding::Base58 { version, hash, checksum } => {
+            println!("  {} Base58Check", "Encoding:".bold());
+            println!("  {} 0x{:02x} {}", "Version Byte:".bold(), version, version_meaning(*version).dimmed());
+            let hash_of = if info.address_type == Some(AddressType::P2sh) { "redeem script" } else { "public key" };
+            println!("  {} {} {}", "Hash160:".bold(), hex::encode(hash).bright_green(), format!("(of the {})", hash_of).dimmed());
+            println!("  {} {} {}", "Checksum:".bold(), hex::encode(checksum).bright_white(),
+                     "(first 4 bytes of SHA256d(version || hash160))".dimmed());
+        }
+        AddressEncoding::Bech32 { hrp, witness_version, program, checksum, bech32m } => {
+            let encoding = if *bech32m { "Bech32m (BIP350)" } else { "Bech32 (BIP173)" };
+            println!("  {} {}", "Encoding:".bold(), encoding);
+            println!("  {} {} {}", "HRP:".bold(), hrp.bright_white(), "(human-readable part, before the '1' separator)".dimmed());
+            println!("  {} {}", "Witness Version:".bold(), witness_version);
+            let program_kind = match info.address_type {
+                Some(AddressType::P2wpkh) => "(HASH160 of the public key)",
+                Some(AddressType::P2wsh) => "(SHA-256 of the witness script)",
+                Some(AddressType::P2tr) => "(Taproot output key, x-only)",
+                _ => "",
+            };
+            println!("  {} {} {}", "Witness Program:".bold(), hex::encode(program).bright_green(), program_kind.dimmed());
+            println!("  {} {} {}", "Checksum:".bold(), checksum.bright_white(), "(last 6 characters, BCH code)".dimmed());
+        }
+    }
+
+    println!("\n{}", "  📜 SCRIPT:".bright_cyan().bold());
+    println!("{}", "  ━".repeat(35).cyan());
+    println!("  {} {}", "scriptPubKey:".bold(), info.script_pubkey.to_hex_string().bright_white());
+    println!("  {} {}", "ASM:".bold(), info.script_pubkey.to_asm_string().bright_white());
+    println!("  {} {}", "Electrum Script Hash:".bold(), hex::encode(breakdown.electrum_script_hash).bright_green());
+    println!("  {} SHA-256 of the scriptPubKey, bytes reversed: the key Electrum servers index by", "ℹ".bright_blue());
+    println!("{}", "  ━".repeat(35).cyan());
+    println!();
+    Ok(())
+}
+
+/// Render an address's hash as other address types and networks
+pub fn run_reencode(input: &str, to: Option<AddressKind>, network: Option<Network>, format: OutputFormat) -> Result<()> {
+    let report = converter::reencode_address(input, to, network)?;
+    let risky: Vec<_> = report.reencodings.iter().filter(|r| r.warning.is_some()).collect();
+    if !format.is_text() {
+        if !risky.is_empty() {
+            eprintln!("{} SegWit addresses from a P2PKH hash are only spendable if the key was compressed",
+                      "⚠️  Warning:".bright_red().bold());
+        }
+        return output::emit(format, &ReencodeOutput::from(&report));
+    }
+
+    println!("\n{}", "━".repeat(70).bright_blue());
+    println!("{}", "  🔁 ADDRESS RE-ENCODING".bright_cyan().bold());
+    println!("{}", "━".repeat(70).bright_blue());
+
+    let info = &report.info;
+    println!("\n  {} {}", "Address:".bold(), info.address.to_string().bright_white());
+    println!("  {} {}", "Type:".bold(), info.type_name().bright_yellow());
+    let hash_kind = match report.kind {
+        AddressKind::P2tr => "Output Key:",
+        AddressKind::P2wsh => "Script Hash:",
+        AddressKind::P2sh => "Script Hash160:",
+        _ => "Key Hash160:",
+    };
+    println!("  {} {}", hash_kind.bold(), hex::encode(&report.hash).bright_green());
+
+    println!("\n{}", "  📋 SAME HASH, OTHER ENCODINGS:".bright_cyan().bold());
+    println!("{}", "  ━".repeat(35).cyan());
+    for reencoding in &report.reencodings {
+        let label = format!("{} ({}):", reencoding.kind, utils::network_name(reencoding.network));
+        let address = reencoding.address.to_string();
+        let address = match (reencoding.is_input, reencoding.warning) {
+            (true, _) => format!("{} {}", address.bright_white(), "(input)".dimmed()),
+            (false, Some(_)) => format!("{} {}", address.bright_red(), "⚠".bright_red().bold()),
+            (false, None) => address.bright_green().to_string(),
+        };
+        println!("  {:<26} {}", label.bold(), address);
+    }
+    println!("{}", "  ━".repeat(35).cyan());
+
+    if !risky.is_empty() {
+        println!("\n{}", "  ⚠️  DANGER: P2PKH → SEGWIT".bright_red().bold());
+        println!("{}", "  ━".repeat(35).bright_red());
+        println!("  {}", "The addresses marked ⚠ are only spendable if the key behind".bright_red());
+        println!("  {}", format!("{} was COMPRESSED.", info.address).bright_red().bold());
+        println!("  A P2PKH hash may be of an uncompressed key (common before 2012). SegWit");
+        println!("  only accepts compressed keys (BIP143): if the key was uncompressed, coins");
+        println!("  sent to these addresses {}.", "can never be spent".bright_red().bold());
+        println!("  Only use them once `btc-tools convert <public key>` shows this P2PKH");
+        println!("  address for the compressed key.");
+        println!("{}", "  ━".repeat(35).bright_red());
+    }
+    println!("\n  {} The same hash on another network is a different chain: nothing moves between them",
+             "ℹ".bright_blue());
+    println!();
+    Ok(())
+}
+
+/// Give the address of a scriptPubKey, or the addresses paying to a redeem script
+pub fn run_script_to_address(input: &str, redeem: bool, network: Network, format: OutputFormat) -> Result<()> {
+    if redeem {
+        let report = script::redeem_script_addresses(input, network)?;
+        if !format.is_text() {
+            return output::emit(format, &ScriptToAddressOutput::from(&report));
+        }
+        display_script_header("REDEEM SCRIPT", &report.script, network);
+
+        println!("\n{}", "  📋 ADDRESSES PAYING TO THIS SCRIPT:".bright_cyan().bold());
+        println!("{}", "  ━".repeat(35).cyan());
+        for (kind, address) in &report.addresses {
+            println!("  {:<14} {}", format!("{}:", kind).bold(), address.to_string().bright_green());
+        }
+        println!("{}", "  ━".repeat(35).cyan());
+        for warning in &report.warnings {
+            println!("  {} {}", "⚠".bright_yellow(), warning.bright_yellow());
+        }
+        println!("  {} Spending reveals the script: keep it, the address alone cannot be spent from", "ℹ".bright_blue());
+    } else {
+        let report = script::script_pubkey_address(input, network)?;
+        if !format.is_text() {
+            return output::emit(format, &ScriptToAddressOutput::from(&report));
+        }
+        display_script_header("SCRIPTPUBKEY", &report.script, network);
+
+        match &report.address {
+            Ok(address) => {
+                println!("\n  {} {}", "Address:".bold(), address.to_string().bright_green());
+                if let Some(address_type) = address.address_type() {
+                    println!("  {} {}", "Type:".bold(), address_type.to_string().bright_yellow());
+                }
+            }
+            Err(reason) => {
+                println!("\n  {} {}", "✗".bright_red(), "No address".bright_red().bold());
+                println!("  {} {}", "ℹ".bright_blue(), reason.explanation());
+                match reason {
+                    NoAddress::P2pk { public_key } => {
+                        println!("  {} {}", "Public Key:".bold(), public_key.to_string().bright_white());
+                    }
+                    NoAddress::NullData { data } if !data.is_empty() => {
+                        println!("  {} {}", "Data:".bold(), hex::encode(data).bright_white());
+                        if let Ok(text) = std::str::from_utf8(data) {
+                            println!("  {} {:?}", "As Text:".bold(), text);
+                        }
+                    }
+                    NoAddress::BareMultisig { .. } | NoAddress::NonStandard => {
+                        println!("  {} btc-tools script-to-address --redeem-script '<script>'", "Try:".bold());
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    println!();
+    Ok(())
+}
+
+fn display_script_header(title: &str, script: &ScriptBuf, network: Network) {
+    println!("\n{}", "━".repeat(70).bright_blue());
+    println!("{}", format!("  📜 {}", title).bright_cyan().bold());
+    println!("{}", "━".repeat(70).bright_blue());
+    println!("\n  {} {}", "Hex:".bold(), script.to_hex_string().bright_white());
+    println!("  {} {}", "ASM:".bold(), script.to_asm_string().bright_white());
+    println!("  {} {} bytes", "Size:".bold(), script.len());
+    println!("  {} {}", "Network:".bold(), utils::network_name(network).bright_white());
+}
+
+/// What a Base58Check version byte stands for
+fn version_meaning(version: u8) -> &'static str {
+    match version {
+        0x00 => "(P2PKH, mainnet)",
+        0x05 => "(P2SH, mainnet)",
+        0x6f => "(P2PKH, testnet/signet/regtest)",
+        0xc4 => "(P2SH, testnet/signet/regtest)",
+        _ => "",
+    }
+}
+
+/// Print a freshly generated example public key
+pub fn run_example(show_usage: bool, format: OutputFormat) -> Result<()> {
+    let pubkey = converter::generate_example_pubkey()?;
+    if !format.is_text() {
+        return output::emit(format, &ExampleOutput { public_key: pubkey.to_string() });
+    }
+
+    println!("\n{}", "🔑 Example Data Generated".bright_cyan().bold());
+    println!("{}", "━".repeat(30).cyan());
+    println!("{} {}", "Sample Public Key (hex):".bold(), pubkey.to_string().bright_white());
+    println!("{}", "━".repeat(30).cyan());
+    println!("You can use this public key with the 'convert' command.");
+    if show_usage {
+        println!("Example: {} convert {}", "btc-tools".green(), pubkey.to_string().bright_white());
+    }
+    Ok(())
+}
+
+fn display_all_formats_from_pubkey(pubkey: &PublicKey, formats: &[Address], network: Network) {
+    let testnet = network != Network::Bitcoin;
+
+    let label = if pubkey.compressed { "Public Key (compressed):" } else { "Public Key (uncompressed):" };
+    println!("\n  {} {}", label.bold(), pubkey.to_string().bright_white());
+    println!();
+
+    println!("{}", "  📋 ALL ADDRESS FORMATS:".bright_cyan().bold());
+    println!("{}", "  ━".repeat(35).cyan());
+
+    for address in formats {
+        match address.address_type() {
+            Some(AddressType::P2pkh) => {
+                let features = if testnet {
+                    "• Starts with 'm' or 'n'\n  • For Testnet use only\n  • Base58Check encoding"
+                } else {
+                    "• Starts with '1'\n  • Most compatible (all wallets support)\n  • Highest transaction fees\n  • Base58Check encoding"
+                };
+                display_format_box("1️⃣  Legacy (P2PKH)", &address.to_string(), features, "🟡");
+            }
+            Some(AddressType::P2sh) => {
+                let features = if testnet {
+                    "• Starts with '2'\n  • For Testnet use only\n  • Lower fees than legacy"
+                } else {
+                    "• Starts with '3'\n  • Backward compatible with old wallets\n  • Lower fees than legacy (~25% savings)\n  • SegWit benefits in P2SH wrapper"
+                };
+                display_format_box("2️⃣  P2SH-SegWit (Wrapped)", &address.to_string(), features, "🟢");
+            }
+            Some(AddressType::P2wpkh) => {
+                let features = if testnet {
+                    "• Starts with 'tb1q'\n  • RECOMMENDED for Testnet\n  • Lowest fees\n  • Bech32 encoding"
+                } else {
+                    "• Starts with 'bc1q'\n  • RECOMMENDED for new wallets\n  • ~40% lower fees than legacy\n  • Bech32 encoding (lowercase)\n  • Most efficient format"
+                };
+                display_format_box("3️⃣  Native SegWit (P2WPKH)", &address.to_string(), features, "🟢");
+            }
+            Some(AddressType::P2tr) => {
+                let features = if testnet {
+                    "• Starts with 'tb1p'\n  • For Testnet use only\n  • Key-path only (BIP86 tweak)\n  • Bech32m encoding"
+                } else {
+                    "• Starts with 'bc1p'\n  • Key-path only (BIP86 tweak, no scripts)\n  • Most advanced privacy and efficiency\n  • Bech32m encoding"
+                };
+                display_format_box("4️⃣  Taproot (P2TR)", &address.to_string(), features, "🟣");
+            }
+            _ => {}
+        }
+    }
+}
+
+fn display_taproot_tweak(taproot: &TaprootTweak) {
+    println!("\n{}", "  🌿 TAPROOT KEY TWEAK (BIP86):".bright_cyan().bold());
+    println!("{}", "  ━".repeat(35).cyan());
+    println!("  {} {}", "Internal Key (x-only):".bold(), taproot.internal_key.to_string().bright_white());
+    println!("  {} {}", "Tweak t:".bold(), taproot.tweak.to_string().bright_white());
+    println!("  {} {}", "Output Key Q:".bold(), taproot.output_key.to_string().bright_green());
+    println!("  {} {}", "Output Key Parity:".bold(), format!("{:?}", taproot.parity).dimmed());
+    println!();
+    println!("  {} t = hashTapTweak(P), committing to no script tree", "•".dimmed());
+    println!("  {} Q = P + t·G, using the even-Y lift of P", "•".dimmed());
+    println!("  {} The address encodes Q as a version 1 witness program", "•".dimmed());
+    println!("{}", "  ━".repeat(35).cyan());
+}
+
+fn display_format_box(title: &str, address: &str, features: &str, icon: &str) {
+    println!("\n  {}", title.bright_yellow().bold());
+    println!("  {}", "─".repeat(35).dimmed());
+    println!("  {} {}", "Address:".bold(), address.bright_green());
+    println!("  {} Features:", "Info:".bold());
+    for line in features.lines() {
+        println!("  {}", line.white());
+    }
+    println!("  {}", icon);
+}
+
+fn display_fee_comparison() {
+    println!("\n{}", "  💰 TRANSACTION FEE COMPARISON (MAINNET):".bright_cyan().bold());
+    println!("{}", "  ━".repeat(40).cyan());
+    println!("  {} (Baseline: 100%)", "Legacy P2PKH:      ".bright_white());
+    println!("  {} (~75%)", "P2SH-SegWit:       ".bright_white());
+    println!("  {} (~60%) ✨ BEST", "Native SegWit:     ".bright_green().bold());
+    println!("  {} (~60%) 🔒 Most Private", "Taproot:           ".bright_green().bold());
+    println!();
+    println!("  {} Fee savings are approximate", "Note:".bold());
+    println!("  {} Actual savings depend on transaction complexity", "•".dimmed());
+    println!("  {} Native SegWit (bc1q) is recommended for most users", "•".dimmed());
+    println!("{}", "  ━".repeat(40).cyan());
+}
+
+fn display_address_info(address: &Address) {
+    println!("\n{}", "  📊 ADDRESS INFORMATION:".bright_cyan().bold());
+    println!("{}", "  ━".repeat(35).cyan());
+
+    println!("  {} {}", "Network:".bold(),
+             utils::network_name(*address.network()).bright_yellow());
+
+    let script = address.script_pubkey();
+    println!("  {} {} bytes", "Script Size:".bold(), script.len());
+
+    // Show what we know about this address type
+    match address.address_type() {
+        Some(AddressType::P2pkh) => {
+            println!("\n  {} This is a Legacy address", "Type:".bold());
+            println!("  {} Could be converted to SegWit for lower fees", "Tip:".bright_blue());
+        }
+        Some(AddressType::P2sh) => {
+            println!("\n  {} This is a P2SH address", "Type:".bold());
+            println!("  {} May contain SegWit or multisig script", "Note:".bright_blue());
+        }
+        Some(AddressType::P2wpkh) => {
+            println!("\n  {} This is a Native SegWit address", "Type:".bold());
+            println!("  {} Optimal for fees! 👍", "Status:".bright_green());
+        }
+        Some(AddressType::P2tr) => {
+            println!("\n  {} This is a Taproot address", "Type:".bold());
+            println!("  {} Most advanced format! 🚀", "Status:".bright_green());
+        }
+        _ => {}
+    }
+
+    println!("{}", "  ━".repeat(35).cyan());
+}
@@ -0,0 +1,101 @@
+use anyhow::Result;
+use bitcoin::Address;
+use btc_address_tools::descriptor::{self, KeySource, ParsedDescriptor};
+use btc_address_tools::utils;
+use clap::Args;
+use colored::*;
+
+use super::output::{self, DescriptorOutput, OutputFormat};
+use super::NetworkArg;
+
+/// Arguments of the `descriptor` command
+#[derive(Args, Debug, Clone)]
+pub struct DescriptorArgs {
+    /// Output descriptor, e.g. "wpkh([73c5da0a/84'/0'/0']xpub.../0/*)#checksum"
+    pub descriptor: String,
+
+    /// First index of the range to derive
+    #[arg(long, default_value = "0")]
+    pub from: u32,
+
+    /// Number of addresses to derive (ignored without a /* range)
+    #[arg(short, long, default_value = "5")]
+    pub count: u32,
+
+    /// Network for the addresses (default: from the keys, mainnet for hex keys)
+    #[arg(short, long, value_enum)]
+    pub network: Option<NetworkArg>,
+}
+
+/// Parse an output descriptor, verify its checksum and derive its addresses
+pub fn run(args: DescriptorArgs, format: OutputFormat) -> Result<()> {
+    let parsed = descriptor::parse_descriptor(&args.descriptor)?;
+    let network = descriptor::resolve_network(&parsed.descriptor, args.network.map(Into::into))?;
+
+    let indexes = if parsed.descriptor.has_wildcard() {
+        args.from..args.from.saturating_add(args.count)
+    } else {
+        0..1
+    };
+    let addresses = indexes
+        .map(|index| Ok((index, parsed.descriptor.derive_address(index, network)?)))
+        .collect::<Result<Vec<(u32, Address)>>>()?;
+
+    if !format.is_text() {
+        return output::emit(format, &DescriptorOutput::new(&parsed, network, &addresses));
+    }
+
+    println!("\n{}", "━".repeat(70).bright_blue());
+    println!("{}", "  📜 OUTPUT DESCRIPTOR".bright_cyan().bold());
+    println!("{}", "━".repeat(70).bright_blue());
+
+    display_summary(&parsed, network);
+
+    println!("\n{}", "  📊 DERIVED ADDRESSES:".bright_cyan().bold());
+    println!("{}", "  ━".repeat(35).cyan());
+    for (index, address) in &addresses {
+        let index = if parsed.descriptor.has_wildcard() { index.to_string() } else { "-".to_string() };
+        println!("  {} {}", format!("{:>6}", index).dimmed(), address.to_string().bright_green());
+    }
+    println!("{}", "  ━".repeat(35).cyan());
+    println!();
+    Ok(())
+}
+
+fn display_summary(parsed: &ParsedDescriptor, network: bitcoin::Network) {
+    let descriptor = &parsed.descriptor;
+
+    println!("\n  {} {}", "Descriptor:".bold(), parsed.with_checksum().bright_white());
+    if parsed.checksum_supplied {
+        println!("  {} {} {}", "Checksum:".bold(), parsed.checksum.bright_green(), "✓ valid".green());
+    } else {
+        println!("  {} {} {}", "Checksum:".bold(), parsed.checksum.bright_yellow(),
+                 "(none supplied - computed)".dimmed());
+    }
+    println!("  {} {}", "Output Type:".bold(), descriptor.output_type().bright_white());
+    println!("  {} {}", "Network:".bold(), utils::network_name(network).bright_white());
+
+    if let descriptor::Descriptor::Multi { threshold, keys, sorted, .. } = descriptor {
+        let order = if *sorted { "keys sorted per address (sortedmulti)" } else { "keys in the order given" };
+        println!("  {} {}-of-{}, {}", "Multisig:".bold(), threshold, keys.len(), order);
+    }
+
+    println!("\n  {}", "Keys:".bold());
+    for key in descriptor.keys() {
+        let origin = match &key.origin {
+            Some((fingerprint, path)) => format!("{} {}", fingerprint, path),
+            None => "no origin".to_string(),
+        };
+        let kind = match &key.key {
+            KeySource::Single(_) => "public key",
+            KeySource::XOnly(_) => "x-only key",
+            KeySource::Extended { wildcard: true, .. } => "extended key, ranged",
+            KeySource::Extended { wildcard: false, .. } => "extended key",
+        };
+        println!("  {} {} {}", "•".bright_yellow(), kind, format!("({})", origin).dimmed());
+    }
+
+    if !descriptor.has_wildcard() {
+        println!("\n  {} No /* range: the descriptor describes a single address", "ℹ".bright_blue());
+    }
+}
@@ -1,6 +1,7 @@
 use anyhow::Result;
 use bitcoin::{Address, Network, PublicKey, PrivateKey};
 use bitcoin::secp256k1::Secp256k1;
+use btc_address_tools::converter;
 use colored::*;
 use std::io::{self, Write};
 
@@ -91,6 +92,9 @@ fn step2_public_key() -> Result<PublicKey> {
     println!("\n  {} Public Key Formats:", "🔓".bright_yellow());
     println!("  {}", "─".repeat(35).dimmed());
     
+    let uncompressed = PublicKey { compressed: false, inner: public_key };
+    println!("  {} {}", "Uncompressed (65 bytes):".bold(), uncompressed.to_string().dimmed());
+    println!("  {} 04 + x-coordinate + y-coordinate", "Note:".dimmed());
     println!("  {} {}", "Compressed (33 bytes):".bold(), pubkey.to_string().bright_green());
     println!("  {} Starts with 02 or 03", "Note:".dimmed());
     println!("  {} Indicates y-coordinate parity", "     ".dimmed());
@@ -99,6 +103,7 @@ fn step2_public_key() -> Result<PublicKey> {
     println!("  {}", "• Reduces size from 65 to 33 bytes".white());
     println!("  {}", "• Saves space in transactions".white());
     println!("  {}", "• Can reconstruct full point from compressed form".white());
+    println!("  {}", "• SegWit and Taproot only accept compressed keys".white());
     
     Ok(pubkey)
 }
@@ -150,6 +155,10 @@ fn step4_address_generation(pubkey: &PublicKey) -> Result<()> {
     println!("  {} Starts with '1'", "Format:".dimmed());
     println!("  {} Base58Check encoding", "Encoding:".dimmed());
     println!("  {} 1 (version) + 20 (hash) + 4 (checksum)", "Structure:".dimmed());
+    let uncompressed = PublicKey { compressed: false, inner: pubkey.inner };
+    println!("  {} {}", "Uncompressed:".dimmed(), Address::p2pkh(&uncompressed, Network::Bitcoin).to_string().dimmed());
+    println!("  {} Same key, different hash, different address - pre-2012 wallets used this one",
+             "            ".dimmed());
     
     // Native SegWit
     println!("\n  {} Native SegWit (P2WPKH)", "2️⃣".bright_cyan());
@@ -159,11 +168,17 @@ fn step4_address_generation(pubkey: &PublicKey) -> Result<()> {
     println!("  {} Starts with 'bc1q'", "Format:".dimmed());
     println!("  {} Bech32 encoding", "Encoding:".dimmed());
     println!("  {} Lower case, error detection", "Features:".dimmed());
+    println!("  {} Compressed keys only: spending with an uncompressed key is non-standard (BIP143)",
+             "Keys:".dimmed());
     
     // Taproot
     println!("\n  {} Taproot (P2TR)", "3️⃣".bright_cyan());
-    println!("  {} bc1p... (example format)", "Address:".bright_green());
+    let taproot = converter::bip86_tweak(pubkey);
+    let p2tr = Address::p2tr_tweaked(taproot.output_key, Network::Bitcoin);
+    println!("  {} {}", "Address:".bold(), p2tr.to_string().bright_green());
     println!("  {} Starts with 'bc1p'", "Format:".dimmed());
+    println!("  {} The x-only key tweaked by hashTapTweak (BIP86), not hashed", "Key:".dimmed());
+    println!("  {} {}", "Output Key:".dimmed(), taproot.output_key.to_string().dimmed());
     println!("  {} Bech32m encoding", "Encoding:".dimmed());
     println!("  {} Most advanced privacy", "Features:".dimmed());
     
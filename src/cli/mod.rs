@@ -0,0 +1,115 @@
+//! Terminal rendering for the `btc-tools` binary
+//!
+//! Each submodule calls into the library and prints its typed results.
+
+use bip39::Language;
+use bitcoin::Network;
+use btc_address_tools::converter::AddressKind;
+use btc_address_tools::hd_wallet::ScriptType;
+use clap::ValueEnum;
+
+pub mod convert;
+pub mod descriptor;
+pub mod educational;
+pub mod output;
+pub mod recover;
+pub mod validate;
+pub mod wallet;
+
+/// Network selected on the command line
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum NetworkArg {
+    Mainnet,
+    Testnet,
+    Signet,
+    Regtest,
+}
+
+impl From<NetworkArg> for Network {
+    fn from(arg: NetworkArg) -> Self {
+        match arg {
+            NetworkArg::Mainnet => Network::Bitcoin,
+            NetworkArg::Testnet => Network::Testnet,
+            NetworkArg::Signet => Network::Signet,
+            NetworkArg::Regtest => Network::Regtest,
+        }
+    }
+}
+
+/// Wallet address type selected on the command line
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ScriptTypeArg {
+    P2pkh,
+    P2shP2wpkh,
+    P2wpkh,
+    P2tr,
+}
+
+impl From<ScriptTypeArg> for ScriptType {
+    fn from(arg: ScriptTypeArg) -> Self {
+        match arg {
+            ScriptTypeArg::P2pkh => ScriptType::P2pkh,
+            ScriptTypeArg::P2shP2wpkh => ScriptType::P2shP2wpkh,
+            ScriptTypeArg::P2wpkh => ScriptType::P2wpkh,
+            ScriptTypeArg::P2tr => ScriptType::P2tr,
+        }
+    }
+}
+
+/// Address type a hash is re-encoded as
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum AddressKindArg {
+    P2pkh,
+    P2shP2wpkh,
+    P2wpkh,
+    P2sh,
+    P2wsh,
+    P2shP2wsh,
+    P2tr,
+}
+
+impl From<AddressKindArg> for AddressKind {
+    fn from(arg: AddressKindArg) -> Self {
+        match arg {
+            AddressKindArg::P2pkh => AddressKind::P2pkh,
+            AddressKindArg::P2shP2wpkh => AddressKind::P2shP2wpkh,
+            AddressKindArg::P2wpkh => AddressKind::P2wpkh,
+            AddressKindArg::P2sh => AddressKind::P2sh,
+            AddressKindArg::P2wsh => AddressKind::P2wsh,
+            AddressKindArg::P2shP2wsh => AddressKind::P2shP2wsh,
+            AddressKindArg::P2tr => AddressKind::P2tr,
+        }
+    }
+}
+
+/// BIP39 word list selected on the command line
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum LanguageArg {
+    English,
+    ChineseSimplified,
+    ChineseTraditional,
+    Czech,
+    French,
+    Italian,
+    Japanese,
+    Korean,
+    Portuguese,
+    Spanish,
+}
+
+impl From<LanguageArg> for Language {
+    fn from(arg: LanguageArg) -> Self {
+        match arg {
+            LanguageArg::English => Language::English,
+            LanguageArg::ChineseSimplified => Language::SimplifiedChinese,
+            LanguageArg::ChineseTraditional => Language::TraditionalChinese,
+            LanguageArg::Czech => Language::Czech,
+            LanguageArg::French => Language::French,
+            LanguageArg::Italian => Language::Italian,
+            LanguageArg::Japanese => Language::Japanese,
+            LanguageArg::Korean => Language::Korean,
+            LanguageArg::Portuguese => Language::Portuguese,
+            LanguageArg::Spanish => Language::Spanish,
+        }
+    }
+}
@@ -0,0 +1,886 @@
+//! Machine-readable output for every subcommand
+//!
+//! The structs below are the documented output schema. Field names are
+//! stable; new fields may be added but existing ones are never renamed.
+
+use anyhow::Result;
+use bitcoin::{Address, AddressType, Network, PublicKey};
+use btc_address_tools::converter::{self, AddressBreakdown, AddressEncoding, CompressedEquivalent, ConversionReport, ReencodeReport,
+                                   TaprootTweak};
+use btc_address_tools::descriptor::{self, ParsedDescriptor};
+use btc_address_tools::entropy::UserEntropy;
+use btc_address_tools::hd_wallet::{self, DerivedAddress, WalletReport, WatchOnlyReport, GAP_LIMIT};
+use btc_address_tools::mnemonic_recovery::MnemonicRecovery;
+use btc_address_tools::path_finder::PathSearch;
+use btc_address_tools::script::{RedeemScriptReport, ScriptPubKeyReport};
+use btc_address_tools::slip132;
+use btc_address_tools::utils;
+use btc_address_tools::recovery::Correction;
+use btc_address_tools::validator::{AddressInfo, ValidationError};
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::Value;
+
+/// Output format selected with the global `--output` flag
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Colored, human-readable text
+    #[default]
+    Text,
+    /// Pretty-printed JSON
+    Json,
+    /// YAML document
+    Yaml,
+    /// One `key<TAB>value` line per field, nested keys joined with '.'
+    /// (a table with one row per address for `validate --file`)
+    Tsv,
+    /// Header row of flattened keys followed by a value row
+    /// (a table with one row per address for `validate --file`)
+    Csv,
+}
+
+impl OutputFormat {
+    pub fn is_text(self) -> bool {
+        self == OutputFormat::Text
+    }
+}
+
+/// Serialize `value` to stdout in a structured format
+pub fn emit<T: Serialize>(format: OutputFormat, value: &T) -> Result<()> {
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(value)?),
+        OutputFormat::Yaml => print!("{}", serde_yaml::to_string(value)?),
+        OutputFormat::Tsv => {
+            let mut rows = Vec::new();
+            flatten("", &serde_json::to_value(value)?, &mut rows);
+            for (key, value) in rows {
+                println!("{}", tsv_row(&[key, value]));
+            }
+        }
+        OutputFormat::Csv => {
+            let mut rows = Vec::new();
+            flatten("", &serde_json::to_value(value)?, &mut rows);
+            let (keys, values): (Vec<String>, Vec<String>) = rows.into_iter().unzip();
+            println!("{}", csv_row(&keys));
+            println!("{}", csv_row(&values));
+        }
+        OutputFormat::Text => unreachable!("text output is rendered by each command"),
+    }
+    Ok(())
+}
+
+/// Streams one record per item: JSON Lines, YAML documents, or table rows
+pub struct RecordWriter {
+    format: OutputFormat,
+    columns: &'static [&'static str],
+}
+
+impl RecordWriter {
+    /// Create a writer, printing the header row for CSV and TSV
+    pub fn new(format: OutputFormat, columns: &'static [&'static str]) -> Self {
+        match format {
+            OutputFormat::Csv => println!("{}", csv_row(columns)),
+            OutputFormat::Tsv => println!("{}", tsv_row(columns)),
+            _ => {}
+        }
+        RecordWriter { format, columns }
+    }
+
+    pub fn write<T: Serialize>(&self, record: &T) -> Result<()> {
+        match self.format {
+            OutputFormat::Json => println!("{}", serde_json::to_string(record)?),
+            OutputFormat::Yaml => print!("---\n{}", serde_yaml::to_string(record)?),
+            OutputFormat::Csv | OutputFormat::Tsv => {
+                let value = serde_json::to_value(record)?;
+                let cells: Vec<String> = self.columns.iter()
+                    .map(|column| match value.get(column) {
+                        None | Some(Value::Null) => String::new(),
+                        Some(Value::String(s)) => s.clone(),
+                        Some(other) => other.to_string(),
+                    })
+                    .collect();
+                if self.format == OutputFormat::Csv {
+                    println!("{}", csv_row(&cells));
+                } else {
+                    println!("{}", tsv_row(&cells));
+                }
+            }
+            OutputFormat::Text => unreachable!("text output is rendered by each command"),
+        }
+        Ok(())
+    }
+}
+
+/// Join cells with commas, quoting any that need it
+fn csv_row<S: AsRef<str>>(cells: &[S]) -> String {
+    cells.iter()
+        .map(|cell| {
+            let cell = cell.as_ref();
+            if cell.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", cell.replace('"', "\"\""))
+            } else {
+                cell.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Join cells with tabs, escaping backslashes, tabs and line breaks as
+/// `\\`, `\t`, `\n` and `\r` so every record stays on one line
+fn tsv_row<S: AsRef<str>>(cells: &[S]) -> String {
+    cells.iter()
+        .map(|cell| {
+            cell.as_ref()
+                .replace('\\', "\\\\")
+                .replace('\t', "\\t")
+                .replace('\n', "\\n")
+                .replace('\r', "\\r")
+        })
+        .collect::<Vec<_>>()
+        .join("\t")
+}
+
+fn flatten(prefix: &str, value: &Value, rows: &mut Vec<(String, String)>) {
+    let join = |key: &str| {
+        if prefix.is_empty() { key.to_string() } else { format!("{}.{}", prefix, key) }
+    };
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                flatten(&join(key), value, rows);
+            }
+        }
+        Value::Array(items) => {
+            for (i, value) in items.iter().enumerate() {
+                flatten(&join(&i.to_string()), value, rows);
+            }
+        }
+        Value::String(s) => rows.push((prefix.to_string(), s.clone())),
+        Value::Null => rows.push((prefix.to_string(), String::new())),
+        other => rows.push((prefix.to_string(), other.to_string())),
+    }
+}
+
+/// `validate` output
+#[derive(Debug, Serialize)]
+pub struct ValidateOutput {
+    pub address: String,
+    pub valid: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// `invalid_character`, `mixed_case`, `bad_checksum`, `wrong_length`,
+    /// `unknown_witness_version`, `wrong_network` or `malformed`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_kind: Option<&'static str>,
+    /// Corrected candidates for likely typos, only present when invalid
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub suggestions: Vec<SuggestionOutput>,
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub details: Option<AddressDetails>,
+}
+
+/// Details shared by `validate` and `convert` when given an address
+#[derive(Debug, Serialize)]
+pub struct AddressDetails {
+    /// `p2pkh`, `p2sh`, `p2wpkh`, `p2wsh`, `p2tr`, or null when unknown
+    pub address_type: Option<String>,
+    pub type_name: String,
+    pub description: String,
+    /// `mainnet`, `testnet`, `signet` or `regtest`
+    pub network: String,
+    /// Every network sharing this encoding, e.g. `["testnet", "signet"]` for `tb1`
+    pub possible_networks: Vec<String>,
+    pub script_type: String,
+    /// scriptPubKey in hex
+    pub script_pubkey: String,
+}
+
+impl ValidateOutput {
+    pub fn valid(info: &AddressInfo) -> Self {
+        ValidateOutput {
+            address: info.input.clone(),
+            valid: true,
+            error: None,
+            error_kind: None,
+            suggestions: Vec::new(),
+            details: Some(AddressDetails::from(info)),
+        }
+    }
+
+    pub fn invalid(address: &str, error: &ValidationError, suggestions: &[Correction]) -> Self {
+        ValidateOutput {
+            address: address.to_string(),
+            valid: false,
+            error: Some(error.to_string()),
+            error_kind: Some(error.kind()),
+            suggestions: suggestions.iter().map(SuggestionOutput::from).collect(),
+            details: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SuggestionOutput {
+    pub address: String,
+    /// 1-based positions of the corrected characters
+    pub positions: Vec<usize>,
+}
+
+impl From<&Correction> for SuggestionOutput {
+    fn from(correction: &Correction) -> Self {
+        SuggestionOutput {
+            address: correction.address.clone(),
+            positions: correction.positions.clone(),
+        }
+    }
+}
+
+/// One line of `validate --file` output
+#[derive(Debug, Serialize)]
+pub struct BatchRecord {
+    /// 1-based line number in the input
+    pub line: usize,
+    #[serde(flatten)]
+    pub result: ValidateOutput,
+}
+
+/// Columns of the `validate --file` CSV and TSV tables
+pub const BATCH_COLUMNS: &[&str] = &[
+    "line", "address", "valid", "error_kind", "error", "address_type", "network", "script_type",
+];
+
+impl From<&AddressInfo> for AddressDetails {
+    fn from(info: &AddressInfo) -> Self {
+        AddressDetails {
+            address_type: info.address_type.map(|t| t.to_string()),
+            type_name: info.type_name().to_string(),
+            description: info.description().to_string(),
+            network: utils::network_name(info.network).to_string(),
+            possible_networks: info.possible_networks.iter().map(|&n| utils::network_name(n).to_string()).collect(),
+            script_type: info.script_type().to_string(),
+            script_pubkey: info.script_pubkey.to_hex_string(),
+        }
+    }
+}
+
+/// `generate` and `import` output
+#[derive(Debug, Serialize)]
+pub struct WalletOutput {
+    /// Only present for `generate`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mnemonic: Option<String>,
+    pub word_count: usize,
+    /// BIP39 word list, e.g. `english` or `japanese`
+    pub language: String,
+    /// `os_rng`, `dice`, `coins` or `hex`, with `+os_rng` when mixed;
+    /// only present for `generate`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entropy_source: Option<String>,
+    /// Bias warnings about user-supplied entropy
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub entropy_warnings: Vec<String>,
+    /// 64-byte BIP39 seed in hex, only present for `generate`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<String>,
+    pub passphrase_protected: bool,
+    pub master_fingerprint: String,
+    pub derivation_path: String,
+    /// Hardened prefix of `derivation_path`
+    pub account_path: String,
+    /// Account extended public key with the SLIP-132 prefix for
+    /// `script_type` (`ypub`, `zpub`, ... or plain `xpub`/`tpub`)
+    pub account_xpub: String,
+    /// `p2pkh`, `p2sh-p2wpkh`, `p2wpkh` or `p2tr`
+    pub script_type: String,
+    pub network: String,
+    /// Output descriptors with checksums: the receive chain, then change
+    pub descriptors: Vec<String>,
+    /// Receive chain, `derivation_path/i`
+    pub addresses: Vec<DerivedAddressOutput>,
+    /// Change chain, null when `derivation_path` does not end in `/0`
+    pub change_path: Option<String>,
+    /// Addresses at the same indexes on `change_path`
+    pub change_addresses: Vec<DerivedAddressOutput>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DerivedAddressOutput {
+    pub index: u32,
+    pub path: String,
+    pub address: String,
+    pub public_key: String,
+    /// Index is past the BIP44 gap limit of 20, so a wallet restored from
+    /// seed only finds it after earlier addresses are used
+    pub beyond_gap_limit: bool,
+    /// Extended private key, only present with `--show-private-keys`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub private_key: Option<String>,
+}
+
+impl WalletOutput {
+    pub fn new(wallet: &WalletReport, include_secrets: bool, show_private: bool) -> Self {
+        WalletOutput {
+            mnemonic: include_secrets.then(|| hd_wallet::mnemonic_phrase(&wallet.mnemonic)),
+            language: hd_wallet::language_name(wallet.mnemonic.language()).to_string(),
+            entropy_source: include_secrets.then(|| "os_rng".to_string()),
+            entropy_warnings: Vec::new(),
+            word_count: wallet.mnemonic.word_count(),
+            seed: include_secrets.then(|| hex::encode(wallet.seed)),
+            passphrase_protected: wallet.has_passphrase,
+            master_fingerprint: wallet.master_fingerprint.to_string(),
+            derivation_path: wallet.derivation_path.to_string(),
+            account_path: wallet.account_path.to_string(),
+            account_xpub: slip132::encode(&wallet.account_xpub, wallet.script_type),
+            script_type: wallet.script_type.to_string(),
+            network: utils::network_name(wallet.network).to_string(),
+            descriptors: descriptor::wallet_descriptors(wallet).iter()
+                .map(|descriptor| descriptor.to_string_with_checksum())
+                .collect(),
+            addresses: wallet.addresses.iter()
+                .map(|derived| DerivedAddressOutput::new(derived, show_private))
+                .collect(),
+            change_path: wallet.change_path.as_ref().map(|path| path.to_string()),
+            change_addresses: wallet.change_addresses.iter()
+                .map(|derived| DerivedAddressOutput::new(derived, show_private))
+                .collect(),
+        }
+    }
+
+    /// Record where `generate` got its entropy, if the user supplied it
+    pub fn with_entropy(mut self, user_entropy: Option<&UserEntropy>) -> Self {
+        if let Some(user_entropy) = user_entropy {
+            let suffix = if user_entropy.mixed_with_os_rng { "+os_rng" } else { "" };
+            self.entropy_source = Some(format!("{}{}", user_entropy.kind, suffix));
+            self.entropy_warnings = user_entropy.warnings.clone();
+        }
+        self
+    }
+}
+
+impl DerivedAddressOutput {
+    fn new(derived: &DerivedAddress, show_private: bool) -> Self {
+        DerivedAddressOutput {
+            index: derived.index,
+            path: derived.path.to_string(),
+            address: derived.address.to_string(),
+            public_key: derived.public_key.to_string(),
+            beyond_gap_limit: derived.index >= GAP_LIMIT,
+            private_key: derived.private_key.filter(|_| show_private).map(|key| key.to_string()),
+        }
+    }
+}
+
+/// `derive-xpub` output
+#[derive(Debug, Serialize)]
+pub struct WatchOnlyOutput {
+    pub xpub: String,
+    pub script_type: String,
+    pub network: String,
+    /// Paths are relative to `xpub`, e.g. `0/3`
+    pub receive: Vec<DerivedAddressOutput>,
+    pub change: Vec<DerivedAddressOutput>,
+}
+
+impl WatchOnlyOutput {
+    pub fn new(encoded: &str, report: &WatchOnlyReport) -> Self {
+        let chain = |addresses: &[DerivedAddress]| addresses.iter()
+            .map(|derived| {
+                let mut output = DerivedAddressOutput::new(derived, false);
+                output.path = output.path.trim_start_matches("m/").to_string();
+                output
+            })
+            .collect();
+        WatchOnlyOutput {
+            xpub: encoded.trim().to_string(),
+            script_type: report.script_type.to_string(),
+            network: utils::network_name(report.network).to_string(),
+            receive: chain(&report.receive),
+            change: chain(&report.change),
+        }
+    }
+}
+
+/// `descriptor` output
+#[derive(Debug, Serialize)]
+pub struct DescriptorOutput {
+    /// The descriptor as supplied, with its checksum
+    pub descriptor: String,
+    pub checksum: String,
+    /// Whether the input carried a checksum (which then matched)
+    pub checksum_supplied: bool,
+    /// e.g. `p2wpkh` or `p2wsh multisig`
+    pub output_type: String,
+    pub network: String,
+    /// Whether the descriptor has a `/*` range
+    pub ranged: bool,
+    pub addresses: Vec<DescriptorAddressOutput>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DescriptorAddressOutput {
+    pub index: u32,
+    pub address: String,
+    /// scriptPubKey in hex
+    pub script_pubkey: String,
+}
+
+impl DescriptorOutput {
+    pub fn new(parsed: &ParsedDescriptor, network: Network, addresses: &[(u32, Address)]) -> Self {
+        DescriptorOutput {
+            descriptor: parsed.with_checksum(),
+            checksum: parsed.checksum.clone(),
+            checksum_supplied: parsed.checksum_supplied,
+            output_type: parsed.descriptor.output_type().to_string(),
+            network: utils::network_name(network).to_string(),
+            ranged: parsed.descriptor.has_wildcard(),
+            addresses: addresses.iter()
+                .map(|(index, address)| DescriptorAddressOutput {
+                    index: *index,
+                    address: address.to_string(),
+                    script_pubkey: address.script_pubkey().to_hex_string(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// `recover-mnemonic` output
+#[derive(Debug, Serialize)]
+pub struct MnemonicRecoveryOutput {
+    pub candidates: Vec<MnemonicCandidateOutput>,
+    /// Phrases tried
+    pub searched: u64,
+    /// Phrases with a valid checksum
+    pub valid_checksums: u64,
+    /// Whether candidates were checked against `--address`
+    pub address_checked: bool,
+    pub unknown_words: Vec<UnknownWordOutput>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MnemonicCandidateOutput {
+    pub mnemonic: String,
+    /// Changes from the input, e.g. "word 3: 'abandn' → 'abandon'"
+    pub fixes: Vec<String>,
+    /// Derivation path of `--address`, when it was found
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address_path: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UnknownWordOutput {
+    /// 1-based position in the input
+    pub position: usize,
+    pub word: String,
+    pub suggestions: Vec<String>,
+}
+
+impl From<&MnemonicRecovery> for MnemonicRecoveryOutput {
+    fn from(recovery: &MnemonicRecovery) -> Self {
+        MnemonicRecoveryOutput {
+            candidates: recovery.candidates.iter()
+                .map(|candidate| MnemonicCandidateOutput {
+                    mnemonic: hd_wallet::mnemonic_phrase(&candidate.mnemonic),
+                    fixes: candidate.fixes.iter().map(ToString::to_string).collect(),
+                    address_path: candidate.address_path.as_ref().map(ToString::to_string),
+                })
+                .collect(),
+            searched: recovery.searched,
+            valid_checksums: recovery.valid_checksums,
+            address_checked: recovery.address_checked,
+            unknown_words: recovery.unknown_words.iter()
+                .map(|unknown| UnknownWordOutput {
+                    position: unknown.position,
+                    word: unknown.word.clone(),
+                    suggestions: unknown.suggestions.iter().map(ToString::to_string).collect(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// `find-path` output
+#[derive(Debug, Serialize)]
+pub struct PathSearchOutput {
+    pub found: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// Name of the layout that matched, e.g. `BIP44/49/84/86`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scheme: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account: Option<u32>,
+    /// `receive` or `change`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index: Option<u32>,
+    pub script_type: String,
+    pub network: String,
+    /// `standard` or `segwit` for an Electrum seed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub electrum_seed: Option<String>,
+    /// Layouts searched
+    pub schemes: Vec<String>,
+    /// Addresses derived and compared
+    pub searched: u64,
+}
+
+impl From<&PathSearch> for PathSearchOutput {
+    fn from(search: &PathSearch) -> Self {
+        let found = search.found.as_ref();
+        PathSearchOutput {
+            found: found.is_some(),
+            path: found.map(|found| found.path.to_string()),
+            scheme: found.map(|found| found.scheme.name.to_string()),
+            account: found.and_then(|found| found.account),
+            chain: found.map(|found| if found.chain == 0 { "receive" } else { "change" }.to_string()),
+            index: found.map(|found| found.index),
+            script_type: search.script_type.to_string(),
+            network: utils::network_name(search.network).to_string(),
+            electrum_seed: search.electrum_seed.map(|seed_type| seed_type.to_string()),
+            schemes: search.schemes.iter().map(ToString::to_string).collect(),
+            searched: search.searched,
+        }
+    }
+}
+
+/// `inspect` output
+#[derive(Debug, Serialize)]
+pub struct InspectOutput {
+    pub address: String,
+    pub address_type: Option<String>,
+    pub network: String,
+    /// `base58check`, `bech32` or `bech32m`
+    pub encoding: String,
+    /// Base58Check only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<u8>,
+    /// Base58Check only: HASH160 of the public key or redeem script
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hash160: Option<String>,
+    /// SegWit only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hrp: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub witness_version: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub witness_program: Option<String>,
+    /// Hex bytes for Base58Check, the last 6 characters for SegWit
+    pub checksum: String,
+    pub script_pubkey: String,
+    pub script_asm: String,
+    pub electrum_script_hash: String,
+}
+
+impl From<&AddressBreakdown> for InspectOutput {
+    fn from(breakdown: &AddressBreakdown) -> Self {
+        let info = &breakdown.info;
+        let mut output = InspectOutput {
+            address: info.address.to_string(),
+            address_type: info.address_type.map(|t| t.to_string()),
+            network: utils::network_name(info.network).to_string(),
+            encoding: String::new(),
+            version: None,
+            hash160: None,
+            hrp: None,
+            witness_version: None,
+            witness_program: None,
+            checksum: String::new(),
+            script_pubkey: info.script_pubkey.to_hex_string(),
+            script_asm: info.script_pubkey.to_asm_string(),
+            electrum_script_hash: hex::encode(breakdown.electrum_script_hash),
+        };
+        match &breakdown.encoding {
+            AddressEncoding::Base58 { version, hash, checksum } => {
+                output.encoding = "base58check".to_string();
+                output.version = Some(*version);
+                output.hash160 = Some(hex::encode(hash));
+                output.checksum = hex::encode(checksum);
+            }
+            AddressEncoding::Bech32 { hrp, witness_version, program, checksum, bech32m } => {
+                output.encoding = if *bech32m { "bech32m" } else { "bech32" }.to_string();
+                output.hrp = Some(hrp.clone());
+                output.witness_version = Some(*witness_version);
+                output.witness_program = Some(hex::encode(program));
+                output.checksum = checksum.clone();
+            }
+        }
+        output
+    }
+}
+
+/// `reencode` output
+#[derive(Debug, Serialize)]
+pub struct ReencodeOutput {
+    pub address: String,
+    pub address_type: String,
+    /// Key hash, script hash or Taproot output key
+    pub hash: String,
+    pub reencodings: Vec<ReencodingOutput>,
+}
+
+/// One rendering of the hash in `reencode` output
+#[derive(Debug, Serialize)]
+pub struct ReencodingOutput {
+    pub address_type: String,
+    pub network: String,
+    pub address: String,
+    /// Whether this is the input address itself
+    pub input: bool,
+    /// `requires_compressed_key`: spendable only if the P2PKH key was compressed
+    pub warning: Option<String>,
+}
+
+impl From<&ReencodeReport> for ReencodeOutput {
+    fn from(report: &ReencodeReport) -> Self {
+        ReencodeOutput {
+            address: report.info.address.to_string(),
+            address_type: report.kind.to_string(),
+            hash: hex::encode(&report.hash),
+            reencodings: report.reencodings.iter().map(|r| ReencodingOutput {
+                address_type: r.kind.to_string(),
+                network: utils::network_name(r.network).to_string(),
+                address: r.address.to_string(),
+                input: r.is_input,
+                warning: r.warning.map(|w| w.to_string()),
+            }).collect(),
+        }
+    }
+}
+
+/// `script-to-address` output, tagged by how the script was read
+#[derive(Debug, Serialize)]
+#[serde(tag = "input", rename_all = "snake_case")]
+pub enum ScriptToAddressOutput {
+    ScriptPubkey {
+        script: String,
+        script_asm: String,
+        network: String,
+        address: Option<String>,
+        address_type: Option<String>,
+        /// Why there is no address: `null_data`, `bare_multisig`, `p2pk` or `non_standard`
+        no_address: Option<String>,
+        explanation: Option<String>,
+    },
+    RedeemScript {
+        script: String,
+        script_asm: String,
+        network: String,
+        /// `p2sh` (scripts up to 520 bytes), `p2sh-p2wsh` and `p2wsh`
+        addresses: Vec<FormatOutput>,
+        warnings: Vec<String>,
+    },
+}
+
+impl From<&ScriptPubKeyReport> for ScriptToAddressOutput {
+    fn from(report: &ScriptPubKeyReport) -> Self {
+        let (address, no_address) = match &report.address {
+            Ok(address) => (Some(address), None),
+            Err(reason) => (None, Some(reason)),
+        };
+        ScriptToAddressOutput::ScriptPubkey {
+            script: report.script.to_hex_string(),
+            script_asm: report.script.to_asm_string(),
+            network: utils::network_name(report.network).to_string(),
+            address: address.map(ToString::to_string),
+            address_type: address.and_then(Address::address_type).map(|t| t.to_string()),
+            no_address: no_address.map(|reason| reason.kind().to_string()),
+            explanation: no_address.map(|reason| reason.explanation()),
+        }
+    }
+}
+
+impl From<&RedeemScriptReport> for ScriptToAddressOutput {
+    fn from(report: &RedeemScriptReport) -> Self {
+        ScriptToAddressOutput::RedeemScript {
+            script: report.script.to_hex_string(),
+            script_asm: report.script.to_asm_string(),
+            network: utils::network_name(report.network).to_string(),
+            addresses: report.addresses.iter()
+                .map(|(kind, address)| FormatOutput { format: kind.to_string(), address: address.to_string() })
+                .collect(),
+            warnings: report.warnings.clone(),
+        }
+    }
+}
+
+/// `convert` output, tagged by the kind of input that was supplied
+#[derive(Debug, Serialize)]
+#[serde(tag = "input", rename_all = "snake_case")]
+pub enum ConvertOutput {
+    PublicKey {
+        network: String,
+        public_key: String,
+        formats: Vec<FormatOutput>,
+        /// Absent for uncompressed keys, which have no Taproot address
+        #[serde(skip_serializing_if = "Option::is_none")]
+        taproot: Option<TaprootOutput>,
+        /// Only for uncompressed keys
+        #[serde(skip_serializing_if = "Option::is_none")]
+        compressed_equivalent: Option<CompressedEquivalentOutput>,
+    },
+    PrivateKey {
+        network: String,
+        /// `wif` or `hex`
+        encoding: String,
+        compressed: bool,
+        public_key: String,
+        formats: Vec<FormatOutput>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        taproot: Option<TaprootOutput>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        compressed_equivalent: Option<CompressedEquivalentOutput>,
+        /// The key re-encoded per network (and compressed, if it is not),
+        /// only with `--show-private-keys`
+        #[serde(skip_serializing_if = "Option::is_none")]
+        wif: Option<Vec<WifOutput>>,
+    },
+    Address {
+        network: String,
+        address: String,
+        address_type: Option<String>,
+        script_pubkey: String,
+    },
+}
+
+#[derive(Debug, Serialize)]
+pub struct FormatOutput {
+    /// `p2pkh`, `p2sh-p2wpkh`, `p2wpkh` or `p2tr`
+    pub format: String,
+    pub address: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WifOutput {
+    /// `mainnet`, or `testnet` for every test network
+    pub network: String,
+    pub compressed: bool,
+    pub wif: String,
+}
+
+/// Compressed form of an uncompressed key and its addresses
+#[derive(Debug, Serialize)]
+pub struct CompressedEquivalentOutput {
+    pub public_key: String,
+    pub formats: Vec<FormatOutput>,
+}
+
+impl From<&CompressedEquivalent> for CompressedEquivalentOutput {
+    fn from(equivalent: &CompressedEquivalent) -> Self {
+        CompressedEquivalentOutput {
+            public_key: equivalent.public_key.to_string(),
+            formats: equivalent.formats.iter().map(FormatOutput::from).collect(),
+        }
+    }
+}
+
+/// BIP86 tweak behind the converter's `p2tr` address
+#[derive(Debug, Serialize)]
+pub struct TaprootOutput {
+    pub internal_key: String,
+    pub tweak: String,
+    pub output_key: String,
+}
+
+impl ConvertOutput {
+    /// Private keys are only included when `show_private` is set
+    pub fn new(report: &ConversionReport, show_private: bool) -> Result<Self> {
+        let taproot = |public_key: &PublicKey| {
+            public_key.compressed.then(|| TaprootOutput::from(&converter::bip86_tweak(public_key)))
+        };
+        let compressed_equivalent = |public_key: &PublicKey, network: Network| -> Result<_> {
+            Ok(converter::compressed_equivalent(public_key, network)?.as_ref().map(CompressedEquivalentOutput::from))
+        };
+        Ok(match report {
+            ConversionReport::PublicKey { network, public_key, formats } => ConvertOutput::PublicKey {
+                network: utils::network_name(*network).to_string(),
+                public_key: public_key.to_string(),
+                formats: formats.iter().map(FormatOutput::from).collect(),
+                taproot: taproot(public_key),
+                compressed_equivalent: compressed_equivalent(public_key, *network)?,
+            },
+            ConversionReport::PrivateKey { network, private_key, encoding, public_key, formats } => ConvertOutput::PrivateKey {
+                network: utils::network_name(*network).to_string(),
+                encoding: encoding.to_string(),
+                compressed: private_key.compressed,
+                public_key: public_key.to_string(),
+                formats: formats.iter().map(FormatOutput::from).collect(),
+                taproot: taproot(public_key),
+                compressed_equivalent: compressed_equivalent(public_key, *network)?,
+                wif: show_private.then(|| converter::wif_encodings(private_key).iter()
+                    .map(|key| WifOutput { network: utils::network_name(key.network).to_string(), compressed: key.compressed, wif: key.to_wif() })
+                    .collect()),
+            },
+            ConversionReport::Address { network, address } => ConvertOutput::Address {
+                network: utils::network_name(*network).to_string(),
+                address: address.to_string(),
+                address_type: address.address_type().map(|t| t.to_string()),
+                script_pubkey: address.script_pubkey().to_hex_string(),
+            },
+        })
+    }
+}
+
+impl From<&TaprootTweak> for TaprootOutput {
+    fn from(taproot: &TaprootTweak) -> Self {
+        TaprootOutput {
+            internal_key: taproot.internal_key.to_string(),
+            tweak: taproot.tweak.to_string(),
+            output_key: taproot.output_key.to_string(),
+        }
+    }
+}
+
+impl From<&Address> for FormatOutput {
+    fn from(address: &Address) -> Self {
+        let format = match address.address_type() {
+            // The converter only produces P2SH as a SegWit wrapper
+            Some(AddressType::P2sh) => "p2sh-p2wpkh".to_string(),
+            Some(other) => other.to_string(),
+            None => "unknown".to_string(),
+        };
+        FormatOutput { format, address: address.to_string() }
+    }
+}
+
+/// `example` output
+#[derive(Debug, Serialize)]
+pub struct ExampleOutput {
+    pub public_key: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tsv_flattening() {
+        let value = serde_json::json!({
+            "valid": true,
+            "addresses": [{ "index": 0, "address": "bc1q" }],
+            "error": null,
+        });
+        let mut rows = Vec::new();
+        flatten("", &value, &mut rows);
+        assert!(rows.contains(&("valid".to_string(), "true".to_string())));
+        assert!(rows.contains(&("addresses.0.address".to_string(), "bc1q".to_string())));
+        assert!(rows.contains(&("error".to_string(), String::new())));
+    }
+
+    #[test]
+    fn test_csv_quoting() {
+        assert_eq!(csv_row(&["a", "b,c", "say \"hi\""]), "a,\"b,c\",\"say \"\"hi\"\"\"");
+        assert_eq!(csv_row(&["a\rb"]), "\"a\rb\"");
+    }
+
+    #[test]
+    fn test_tsv_escaping() {
+        // A `validate --file` line with a tab and a carriage return keeps its columns
+        let row = tsv_row(&["1", "1A1z\tP1e\r", "false", "C:\\keys"]);
+        assert_eq!(row, "1\t1A1z\\tP1e\\r\tfalse\tC:\\\\keys");
+        assert_eq!(row.split('\t').count(), 4);
+        assert!(!row.contains(['\n', '\r']));
+    }
+}
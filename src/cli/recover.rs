@@ -0,0 +1,183 @@
+use anyhow::Result;
+use btc_address_tools::hd_wallet;
+use btc_address_tools::mnemonic_recovery::{self, MnemonicRecovery, MAX_CANDIDATES};
+use btc_address_tools::path_finder::{self, PathSearch};
+use btc_address_tools::utils;
+use clap::Args;
+use colored::*;
+
+use super::output::{self, MnemonicRecoveryOutput, OutputFormat, PathSearchOutput};
+use super::wallet::{read_passphrase, MnemonicArgs};
+use super::LanguageArg;
+
+/// Arguments of the `recover-mnemonic` command
+#[derive(Args, Debug, Clone)]
+pub struct RecoverMnemonicArgs {
+    #[command(flatten)]
+    pub mnemonic: MnemonicArgs,
+
+    /// Number of words missing at an unknown position (0 or 1); write ? in the mnemonic for a word missing at a known position
+    #[arg(long, default_value = "0", value_parser = clap::value_parser!(u8).range(0..=1))]
+    pub missing: u8,
+
+    /// A receive or change address of the wallet, to single out the right candidate
+    #[arg(short, long)]
+    pub address: Option<String>,
+
+    /// Word list of the mnemonic
+    #[arg(short, long, value_enum, default_value = "english")]
+    pub language: LanguageArg,
+
+    /// BIP39 passphrase used with --address; prompts without echo if no value is given
+    #[arg(long, value_name = "PASSPHRASE", num_args = 0..=1, requires = "address")]
+    pub passphrase: Option<Option<String>>,
+}
+
+/// Arguments of the `find-path` command
+#[derive(Args, Debug, Clone)]
+pub struct FindPathArgs {
+    #[command(flatten)]
+    pub mnemonic: MnemonicArgs,
+
+    /// Address the wallet is known to have
+    #[arg(short, long)]
+    pub address: String,
+
+    /// Word list of the mnemonic (default: detected from the words)
+    #[arg(short, long, value_enum)]
+    pub language: Option<LanguageArg>,
+
+    /// BIP39 or Electrum passphrase; prompts without echo if no value is given
+    #[arg(long, value_name = "PASSPHRASE", num_args = 0..=1)]
+    pub passphrase: Option<Option<String>>,
+
+    /// Accounts to search on BIP44/49/84/86 paths
+    #[arg(long, default_value = "5")]
+    pub accounts: u32,
+
+    /// Addresses to search on each receive and change chain
+    #[arg(short, long, default_value = "100")]
+    pub count: u32,
+}
+
+/// Search for the valid mnemonics a damaged one may have been
+pub fn run(args: RecoverMnemonicArgs, format: OutputFormat) -> Result<()> {
+    let mnemonic_str = args.mnemonic.read()?;
+    let passphrase = read_passphrase(args.passphrase)?;
+    let recovery = mnemonic_recovery::recover_mnemonic(
+        &mnemonic_str, args.missing.into(), args.language.into(), args.address.as_deref(), &passphrase,
+    )?;
+
+    if !format.is_text() {
+        return output::emit(format, &MnemonicRecoveryOutput::from(&recovery));
+    }
+
+    println!("\n{}", "━".repeat(70).bright_blue());
+    println!("{}", "  🩹 MNEMONIC RECOVERY".bright_cyan().bold());
+    println!("{}", "━".repeat(70).bright_blue());
+
+    for unknown in &recovery.unknown_words {
+        let suggestions = match unknown.suggestions.is_empty() {
+            true => "nothing close - trying every word".dimmed().to_string(),
+            false => unknown.suggestions.join(", "),
+        };
+        println!("\n  {} Word {} '{}' is not in the word list: {}",
+                 "⚠".bright_yellow(), unknown.position, unknown.word.bright_red(), suggestions);
+    }
+
+    println!("\n  {} {} phrases tried, {} with a valid checksum",
+             "Searched:".bold(), recovery.searched, recovery.valid_checksums);
+    display_candidates(&recovery);
+    println!();
+    Ok(())
+}
+
+fn display_candidates(recovery: &MnemonicRecovery) {
+    if recovery.candidates.is_empty() {
+        let reason = if recovery.address_checked {
+            "None of the valid phrases derives the address within the gap limit of its standard account"
+        } else {
+            "No valid phrase found"
+        };
+        println!("\n  {} {}", "✗".bright_red(), reason.bright_red());
+        println!("  {} Mark missing words with ?, try --missing, or check the --language and --passphrase",
+                 "ℹ".bright_blue());
+        return;
+    }
+
+    let heading = if recovery.address_checked { "  ✅ MATCHING MNEMONIC:" } else { "  📋 CANDIDATES:" };
+    println!("\n{}", heading.bright_green().bold());
+    println!("{}", "  ━".repeat(35).cyan());
+    for (i, candidate) in recovery.candidates.iter().enumerate() {
+        println!("  {} {}", format!("{:>3}.", i + 1).dimmed(), hd_wallet::mnemonic_phrase(&candidate.mnemonic).bright_white());
+        for fix in &candidate.fixes {
+            println!("       {} {}", "•".bright_yellow(), fix);
+        }
+        if let Some(path) = &candidate.address_path {
+            println!("       {} address found at {}", "✓".green(), path.to_string().bright_green());
+        }
+    }
+    println!("{}", "  ━".repeat(35).cyan());
+
+    if !recovery.address_checked && recovery.valid_checksums > 1 {
+        if recovery.valid_checksums as usize > MAX_CANDIDATES {
+            println!("  {} Showing {} of {} candidates", "ℹ".bright_blue(), MAX_CANDIDATES, recovery.valid_checksums);
+        }
+        println!("  {} The checksum alone cannot tell these apart; pass --address with one of the wallet's addresses",
+                 "ℹ".bright_blue());
+    }
+}
+
+/// Search common derivation paths for the one that produced an address
+pub fn run_find_path(args: FindPathArgs, format: OutputFormat) -> Result<()> {
+    let mnemonic_str = args.mnemonic.read()?;
+    let passphrase = read_passphrase(args.passphrase)?;
+    let search = path_finder::find_path(
+        &mnemonic_str, args.language.map(Into::into), &passphrase, &args.address, args.accounts, args.count,
+    )?;
+
+    if !format.is_text() {
+        return output::emit(format, &PathSearchOutput::from(&search));
+    }
+
+    println!("\n{}", "━".repeat(70).bright_blue());
+    println!("{}", "  🧭 DERIVATION PATH SEARCH".bright_cyan().bold());
+    println!("{}", "━".repeat(70).bright_blue());
+
+    println!("\n  {} {} ({})", "Address:".bold(), args.address.bright_white(), search.script_type);
+    println!("  {} {}", "Network:".bold(), utils::network_name(search.network).bright_white());
+    if let Some(seed_type) = search.electrum_seed {
+        println!("  {} Electrum {} seed (not BIP39)", "Seed:".bold(), seed_type);
+    }
+    if !search.schemes.is_empty() {
+        println!("  {} {} addresses on {}", "Searched:".bold(), search.searched, search.schemes.join(", "));
+    }
+
+    display_path_match(&search, args.accounts, args.count);
+    println!();
+    Ok(())
+}
+
+fn display_path_match(search: &PathSearch, accounts: u32, count: u32) {
+    if search.schemes.is_empty() {
+        println!("\n  {} No known layout gives {} addresses from this seed", "✗".bright_red(), search.script_type);
+        return;
+    }
+    let Some(found) = &search.found else {
+        println!("\n  {} {}", "✗".bright_red(), "No searched path derives the address".bright_red());
+        println!("  {} Searched accounts 0-{} and indexes 0-{} of each chain; raise --accounts or --count,",
+                 "ℹ".bright_blue(), accounts.saturating_sub(1), count.saturating_sub(1));
+        println!("    or check the passphrase: a wrong one gives a valid but different wallet");
+        return;
+    };
+
+    println!("\n{}", "  ✅ FOUND:".bright_green().bold());
+    println!("  {} {}", "Path:".bold(), found.path.to_string().bright_green());
+    println!("  {} {}", "Layout:".bold(), found.scheme.name.bright_white());
+    println!("  {} {}", "Used by:".bold(), found.scheme.used_by);
+    if let Some(account) = found.account {
+        println!("  {} {}", "Account:".bold(), account);
+    }
+    let chain = if found.chain == 0 { "receive" } else { "change" };
+    println!("  {} {} #{}", "Address:".bold(), chain, found.index);
+}
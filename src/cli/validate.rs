@@ -0,0 +1,305 @@
+use anyhow::{Context, Result};
+use bitcoin::{AddressType, Network};
+use btc_address_tools::recovery::Correction;
+use btc_address_tools::utils;
+use btc_address_tools::validator::{self, AddressInfo, BatchSummary, ValidationError};
+use colored::*;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::process::ExitCode;
+
+use super::output::{self, BatchRecord, OutputFormat, RecordWriter, ValidateOutput, BATCH_COLUMNS};
+
+/// Exit code for a batch in which at least one address was invalid
+pub const BATCH_INVALID_EXIT_CODE: u8 = 10;
+
+/// Validate a Bitcoin address and display detailed information
+///
+/// Returns a non-zero exit code identifying the failure category when the
+/// address is invalid.
+pub fn run(address_str: &str, network: Option<Network>, format: OutputFormat) -> Result<ExitCode> {
+    let result = validator::validate_address(address_str, network);
+    let code = match &result {
+        Ok(_) => ExitCode::SUCCESS,
+        Err(e) => ExitCode::from(exit_code(e)),
+    };
+
+    if !format.is_text() {
+        let report = match &result {
+            Ok(info) => ValidateOutput::valid(info),
+            Err(e) => ValidateOutput::invalid(address_str, e, &validator::suggest_corrections(address_str, e)),
+        };
+        output::emit(format, &report)?;
+        return Ok(code);
+    }
+
+    println!("\n{}", "━".repeat(70).bright_blue());
+    println!("{}", "  🔍 VALIDATING BITCOIN ADDRESS".bright_cyan().bold());
+    println!("{}", "━".repeat(70).bright_blue());
+
+    match result {
+        Ok(info) => display_valid_address(&info),
+        Err(e) => display_invalid_address(address_str, &e),
+    }
+
+    Ok(code)
+}
+
+/// Validate one address per line from a file, or stdin when `path` is `-`
+///
+/// Results are streamed as they are produced. Structured formats get one
+/// record per address on stdout, with the summary on stderr.
+pub fn run_batch(path: &str, network: Option<Network>, format: OutputFormat) -> Result<ExitCode> {
+    let reader: Box<dyn BufRead> = if path == "-" {
+        Box::new(io::stdin().lock())
+    } else {
+        let file = File::open(path).with_context(|| format!("Failed to open {}", path))?;
+        Box::new(BufReader::new(file))
+    };
+
+    let writer = (!format.is_text()).then(|| RecordWriter::new(format, BATCH_COLUMNS));
+    let mut summary = BatchSummary::new(network);
+
+    for entry in validator::validate_lines(reader, network) {
+        let entry = entry.with_context(|| format!("Failed to read {}", path))?;
+        summary.record(&entry.result);
+
+        match &writer {
+            Some(writer) => {
+                let result = match &entry.result {
+                    Ok(info) => ValidateOutput::valid(info),
+                    Err(e) => ValidateOutput::invalid(&entry.input, e, &[]),
+                };
+                writer.write(&BatchRecord { line: entry.line, result })?;
+            }
+            None => display_batch_entry(entry.line, &entry.input, &entry.result),
+        }
+    }
+
+    if format.is_text() {
+        println!("{}", format_batch_summary(&summary));
+    } else {
+        eprintln!("{}", format_batch_summary(&summary));
+    }
+
+    Ok(if summary.invalid > 0 { ExitCode::from(BATCH_INVALID_EXIT_CODE) } else { ExitCode::SUCCESS })
+}
+
+/// Process exit code for each validation failure category
+///
+/// 1 and 2 are left to general errors and usage errors respectively.
+pub fn exit_code(error: &ValidationError) -> u8 {
+    match error {
+        ValidationError::InvalidCharacter { .. } => 3,
+        ValidationError::MixedCase => 4,
+        ValidationError::BadChecksum => 5,
+        ValidationError::WrongLength(_) => 6,
+        ValidationError::UnknownWitnessVersion(_) => 7,
+        ValidationError::WrongNetwork { .. } => 8,
+        ValidationError::Malformed(_) => 9,
+    }
+}
+
+fn display_batch_entry(line: usize, input: &str, result: &Result<AddressInfo, ValidationError>) {
+    let line = format!("{:>6}", line).dimmed();
+    match result {
+        Ok(info) => println!("{} {} {}  {} {}", line, "✓".bright_green(), input.bright_white(),
+                             info.type_name().bright_yellow(), get_network_display(info.network)),
+        Err(e) => println!("{} {} {}  {}", line, "✗".bright_red(), input.white(), e.to_string().red()),
+    }
+}
+
+fn format_batch_summary(summary: &BatchSummary) -> String {
+    let mut lines = vec![
+        String::new(),
+        format!("{}", "  📊 BATCH SUMMARY".bright_cyan().bold()),
+        format!("{}", "  ━".repeat(35).cyan()),
+        format!("  {} {}", "Total:".bold(), summary.total),
+        format!("  {} {}", "Valid:".bold(), summary.valid.to_string().bright_green()),
+        format!("  {} {}", "Invalid:".bold(), summary.invalid.to_string().bright_red()),
+    ];
+
+    let mut section = |title: &str, counts: Vec<(&str, usize)>| {
+        if !counts.is_empty() {
+            lines.push(format!("\n  {}", title.bold()));
+            for (key, count) in counts {
+                lines.push(format!("    {:24} {}", key, count));
+            }
+        }
+    };
+    section("By type:", summary.by_type.iter().map(|(k, v)| (k.as_str(), *v)).collect());
+    section("By network:", summary.by_network.iter().map(|(k, v)| (k.as_str(), *v)).collect());
+    section("By error:", summary.by_error.iter().map(|(k, v)| (*k, *v)).collect());
+
+    lines.push(format!("{}", "  ━".repeat(35).cyan()));
+    lines.join("\n")
+}
+
+fn display_valid_address(info: &AddressInfo) {
+    println!("\n{}", "✅ VALID ADDRESS".bright_green().bold());
+    println!();
+
+    // Display address information
+    println!("  {} {}", "Address:".bold(), info.input.bright_white());
+    println!("  {} {}", "Type:".bold(), info.type_name().bright_yellow());
+    println!("  {} {}", "Description:".bold(), info.description().white());
+    println!("  {} {}", "Network:".bold(), get_network_display(info.network));
+    if info.is_network_ambiguous() {
+        println!("  {} Also valid on {} - these networks share an encoding and cannot be told apart",
+                 "!".yellow(), utils::join_network_names(&info.possible_networks).bright_yellow());
+        println!("  {} Use --network to check against a specific one", "ℹ".bright_blue());
+    }
+
+    // Display technical details
+    println!("\n{}", "  📋 Technical Details:".bright_cyan().bold());
+
+    // Show script type
+    println!("  {} {}", "Script Type:".bold(), get_script_type_display(info));
+    println!("  {} {} bytes", "Script Length:".bold(), info.script_pubkey.len());
+    println!("  {} {}", "Script (hex):".bold(), info.script_pubkey.to_hex_string().dimmed());
+
+    // Address characteristics
+    println!("\n{}", "  💡 Characteristics:".bright_cyan().bold());
+
+    match info.address_type {
+        Some(AddressType::P2pkh) => {
+            println!("  {} Legacy format (most compatible)", "•".yellow());
+            println!("  {} Higher transaction fees", "•".yellow());
+            println!("  {} Starts with '1'", "•".yellow());
+            println!("  {} Base58Check encoding", "•".yellow());
+            println!("  {} Hash of a compressed or uncompressed key: one private key has two P2PKH addresses",
+                     "•".yellow());
+        }
+        Some(AddressType::P2sh) => {
+            println!("  {} Script Hash format", "•".yellow());
+            println!("  {} Can contain any script", "•".yellow());
+            println!("  {} Starts with '3'", "•".yellow());
+            println!("  {} Often used for multisig or SegWit", "•".yellow());
+        }
+        Some(AddressType::P2wpkh) => {
+            println!("  {} Native SegWit (recommended)", "•".green());
+            println!("  {} ~40% lower transaction fees", "•".green());
+            println!("  {} Starts with 'bc1q'", "•".green());
+            println!("  {} Bech32 encoding", "•".green());
+            println!("  {} Always a compressed key (BIP143 makes uncompressed keys non-standard)", "•".green());
+        }
+        Some(AddressType::P2wsh) => {
+            println!("  {} Native SegWit Script", "•".green());
+            println!("  {} Lower fees than legacy", "•".green());
+            println!("  {} Starts with 'bc1q'", "•".green());
+            println!("  {} For complex scripts", "•".green());
+        }
+        Some(AddressType::P2tr) => {
+            println!("  {} Taproot (most advanced)", "•".bright_green());
+            println!("  {} Enhanced privacy", "•".bright_green());
+            println!("  {} More efficient multisig", "•".bright_green());
+            println!("  {} Starts with 'bc1p'", "•".bright_green());
+            println!("  {} Bech32m encoding", "•".bright_green());
+        }
+        None => {
+            println!("  {} Unknown address type", "•".red());
+        }
+        Some(_) => {
+            println!("  {} Other address type", "•".yellow());
+        }
+    }
+
+    // Compatibility notes
+    println!("\n{}", "  🔌 Compatibility:".bright_cyan().bold());
+    match info.address_type {
+        Some(AddressType::P2pkh) | Some(AddressType::P2sh) => {
+            println!("  {} Compatible with all wallets", "✓".green());
+        }
+        Some(AddressType::P2wpkh) | Some(AddressType::P2wsh) => {
+            println!("  {} Most modern wallets (post-2017)", "✓".green());
+            println!("  {} May not work with very old software", "!".yellow());
+        }
+        Some(AddressType::P2tr) => {
+            println!("  {} Newest wallets (post-2021)", "✓".green());
+            println!("  {} Not supported by older wallets", "!".yellow());
+        }
+        None => {}
+        Some(_) => {}
+    }
+
+    println!();
+}
+
+fn display_invalid_address(address_str: &str, error: &ValidationError) {
+    if let ValidationError::WrongNetwork { required, found } = error {
+        println!("\n{}", "❌ WRONG NETWORK".bright_red().bold());
+        println!();
+        println!("  {} {}", "Address:".bold(), address_str.bright_white());
+        println!("  {} {}", "Required:".bold(), utils::network_name(*required).bright_yellow());
+        println!("  {} {}", "Address is for:".bold(), utils::join_network_names(found).red());
+        println!("\n  {} Sending to an address from another network will fail or lose funds", "⚠".yellow());
+        println!();
+        return;
+    }
+
+    println!("\n{}", "❌ INVALID ADDRESS".bright_red().bold());
+    println!();
+    println!("  {} {}", "Address:".bold(), address_str.bright_white());
+    println!("  {} {}", "Error:".bold(), error.to_string().red());
+
+    let corrections = validator::suggest_corrections(address_str, error);
+    if !corrections.is_empty() {
+        display_corrections(&corrections);
+    }
+
+    // Provide helpful hints
+    println!("\n{}", "  💡 Common Issues:".bright_yellow().bold());
+    println!("  {} Check for typos or missing characters", "•".yellow());
+    println!("  {} Valid formats:", "•".yellow());
+    println!("      {} 1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa", "-".dimmed());
+    println!("      {} 3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLy", "-".dimmed());
+    println!("      {} bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq", "-".dimmed());
+    println!("      {} bc1p5d7rjq7g6rdk2yhzks9smlaqtedr4dekq08ge8ztwac72sfr9rusxg3297", "-".dimmed());
+    println!("  {} Testnet addresses start with 'm', 'n', '2', 'tb1'", "•".yellow());
+    println!();
+}
+
+fn display_corrections(corrections: &[Correction]) {
+    println!("\n{}", "  🔧 Did you mean:".bright_green().bold());
+    for correction in corrections {
+        // Highlight the characters that were changed
+        let highlighted: String = correction.address.chars()
+            .enumerate()
+            .map(|(i, c)| {
+                if correction.positions.contains(&(i + 1)) {
+                    c.to_string().bright_green().bold().underline().to_string()
+                } else {
+                    c.to_string().white().to_string()
+                }
+            })
+            .collect();
+        let positions: Vec<String> = correction.positions.iter().map(|p| p.to_string()).collect();
+        println!("  {} {}  {}", "•".green(), highlighted,
+                 format!("(changed position {})", positions.join(", ")).dimmed());
+    }
+    println!("  {} Always confirm a corrected address with its owner before sending", "⚠".yellow());
+}
+
+fn get_network_display(network: Network) -> ColoredString {
+    match network {
+        Network::Bitcoin => "Mainnet (Production)".bright_green(),
+        Network::Testnet => "Testnet (Testing)".bright_yellow(),
+        Network::Signet => "Signet (Testing)".bright_yellow(),
+        Network::Regtest => "Regtest (Local)".bright_magenta(),
+        _ => "Unknown Network".red(),
+    }
+}
+
+fn get_script_type_display(info: &AddressInfo) -> ColoredString {
+    let script = &info.script_pubkey;
+    let name = info.script_type();
+    if script.is_p2pkh() || script.is_p2sh() {
+        name.bright_yellow()
+    } else if script.is_p2wpkh() || script.is_p2wsh() {
+        name.bright_green()
+    } else if script.is_p2tr() {
+        name.bright_cyan()
+    } else {
+        name.red()
+    }
+}
@@ -0,0 +1,562 @@
+use anyhow::{Context, Result};
+use bitcoin::bip32::ChildNumber;
+use bitcoin::Network;
+use btc_address_tools::hd_wallet::{self, DerivedAddress, ScriptType, WalletReport, WatchOnlyReport, GAP_LIMIT};
+use btc_address_tools::entropy::{self, EntropyKind, UserEntropy};
+use btc_address_tools::{descriptor, slip132, utils};
+use clap::Args;
+use std::io::IsTerminal;
+use std::ops::Range;
+use std::path::PathBuf;
+use colored::*;
+
+use super::output::{self, OutputFormat, WalletOutput, WatchOnlyOutput};
+use super::{LanguageArg, NetworkArg, ScriptTypeArg};
+
+/// Arguments of the `generate` command
+#[derive(Args, Debug, Clone)]
+pub struct GenerateArgs {
+    /// Number of words in mnemonic (12, 15, 18, 21 or 24)
+    #[arg(short, long, default_value = "12", value_parser = parse_word_count)]
+    pub words: usize,
+
+    /// Word list for the mnemonic
+    #[arg(short, long, value_enum, default_value = "english")]
+    pub language: LanguageArg,
+
+    /// Address type, derived from its standard path (BIP44/49/84/86)
+    #[arg(short, long, value_enum, default_value = "p2wpkh")]
+    pub script_type: ScriptTypeArg,
+
+    #[command(flatten)]
+    pub entropy: EntropyArgs,
+
+    #[command(flatten)]
+    pub keys: KeyArgs,
+}
+
+/// User-supplied randomness for `generate`, instead of the OS RNG alone
+#[derive(Args, Debug, Clone, Default)]
+pub struct EntropyArgs {
+    /// Build the mnemonic from d6 rolls, digits 1-6 (SHA-256 of the rolls; 50+ for 12 words, 100+ for 24)
+    #[arg(long, value_name = "ROLLS", group = "user_entropy")]
+    pub entropy_dice: Option<String>,
+
+    /// Build the mnemonic from coin flips, H/T or 1/0 (one per bit: 128 for 12 words, 256 for 24)
+    #[arg(long, value_name = "FLIPS", group = "user_entropy")]
+    pub entropy_coins: Option<String>,
+
+    /// Build the mnemonic from raw entropy in hex (32 characters for 12 words, 64 for 24)
+    #[arg(long, value_name = "HEX", group = "user_entropy")]
+    pub entropy_hex: Option<String>,
+
+    /// XOR the supplied entropy with the OS RNG (no longer reproducible from the input alone)
+    #[arg(long, requires = "user_entropy")]
+    pub xor_os_rng: bool,
+}
+
+impl EntropyArgs {
+    /// Entropy from whichever input was given, `None` to use the OS RNG
+    fn resolve(&self, word_count: usize) -> Result<Option<UserEntropy>> {
+        let mut entropy = match (&self.entropy_dice, &self.entropy_coins, &self.entropy_hex) {
+            (Some(rolls), _, _) => entropy::from_dice(rolls, word_count)?,
+            (_, Some(flips), _) => entropy::from_coins(flips, word_count)?,
+            (_, _, Some(hex)) => entropy::from_hex(hex, word_count)?,
+            _ => return Ok(None),
+        };
+        if self.xor_os_rng {
+            entropy.mix_os_rng();
+        }
+        Ok(Some(entropy))
+    }
+}
+
+/// Environment variable the mnemonic is read from when no flag gives one
+pub const MNEMONIC_ENV: &str = "BTC_TOOLS_MNEMONIC";
+
+/// Where a mnemonic comes from, shared by every command that reads one
+///
+/// Without a flag or the environment variable the mnemonic is prompted for
+/// without echo, or read from stdin when it is not a terminal.
+#[derive(Args, Debug, Clone, Default)]
+pub struct MnemonicArgs {
+    /// Mnemonic phrase (⚠️ kept in shell history and shown by `ps`; prefer the prompt, --mnemonic-file or $BTC_TOOLS_MNEMONIC)
+    #[arg(short, long)]
+    pub mnemonic: Option<String>,
+
+    /// Read the mnemonic from a file ("-" reads stdin)
+    #[arg(long, value_name = "PATH", conflicts_with = "mnemonic")]
+    pub mnemonic_file: Option<PathBuf>,
+}
+
+/// Arguments of the `import` command
+#[derive(Args, Debug, Clone)]
+pub struct ImportArgs {
+    #[command(flatten)]
+    pub mnemonic: MnemonicArgs,
+
+    /// Word list of the mnemonic (default: detected from the words)
+    #[arg(short, long, value_enum)]
+    pub language: Option<LanguageArg>,
+
+    /// Custom derivation path (default: the standard path for --script-type, else m/84'/0'/0'/0;
+    /// earlier releases used m/44'/0'/0'/0 with bc1q addresses, see --script-type p2wpkh)
+    #[arg(short, long)]
+    pub path: Option<String>,
+
+    /// Address type (default: inferred from the path's purpose, else p2wpkh)
+    #[arg(short, long, value_enum)]
+    pub script_type: Option<ScriptTypeArg>,
+
+    #[command(flatten)]
+    pub keys: KeyArgs,
+}
+
+/// Key derivation and display options shared by `generate` and `import`
+#[derive(Args, Debug, Clone)]
+pub struct KeyArgs {
+    /// Number of addresses to derive on each chain
+    #[arg(short, long, default_value = "5")]
+    pub count: u32,
+
+    /// First address index to derive
+    #[arg(long, default_value = "0")]
+    pub from: u32,
+
+    /// BIP39 passphrase ("25th word"); prompts without echo if no value is given
+    #[arg(long, value_name = "PASSPHRASE", num_args = 0..=1)]
+    pub passphrase: Option<Option<String>>,
+
+    /// Network for coin type, extended key version (xprv/tprv) and addresses
+    #[arg(short, long, value_enum, default_value = "mainnet")]
+    pub network: NetworkArg,
+
+    /// Show private keys (⚠️ USE WITH CAUTION)
+    #[arg(long)]
+    pub show_private_keys: bool,
+}
+
+/// Arguments of the `derive-xpub` command
+#[derive(Args, Debug, Clone)]
+pub struct DeriveXpubArgs {
+    /// Account extended public key (xpub, ypub, zpub, tpub, upub or vpub)
+    pub xpub: String,
+
+    /// Address type (default: implied by the key prefix, p2pkh for xpub/tpub)
+    #[arg(short, long, value_enum)]
+    pub script_type: Option<ScriptTypeArg>,
+
+    /// Number of addresses to derive on each chain
+    #[arg(short, long, default_value = "5")]
+    pub count: u32,
+
+    /// First address index to derive
+    #[arg(long, default_value = "0")]
+    pub from: u32,
+
+    /// Network for the addresses (default: from the key; tpub keys may use signet or regtest)
+    #[arg(short, long, value_enum)]
+    pub network: Option<NetworkArg>,
+}
+
+impl KeyArgs {
+    /// Address indexes selected by `--from` and `--count`
+    pub fn indexes(&self) -> Range<u32> {
+        self.from..self.from.saturating_add(self.count)
+    }
+}
+
+/// Generate a new HD wallet with BIP39 mnemonic
+pub fn run_generate(args: GenerateArgs, format: OutputFormat) -> Result<()> {
+    let indexes = args.keys.indexes();
+    let KeyArgs { passphrase, network, show_private_keys: show_private, .. } = args.keys;
+    let user_entropy = args.entropy.resolve(args.words)?;
+    let passphrase = read_passphrase(passphrase)?;
+    let generate = || match &user_entropy {
+        Some(user_entropy) => hd_wallet::wallet_from_entropy(
+            &user_entropy.entropy, args.language.into(), indexes.clone(), args.script_type.into(), &passphrase,
+            network.into(),
+        ),
+        None => hd_wallet::generate_wallet(
+            args.words, args.language.into(), indexes.clone(), args.script_type.into(), &passphrase, network.into(),
+        ),
+    };
+
+    if !format.is_text() {
+        let output = WalletOutput::new(&generate()?, true, show_private).with_entropy(user_entropy.as_ref());
+        return output::emit(format, &output);
+    }
+
+    println!("\n{}", "━".repeat(70).bright_blue());
+    println!("{}", "  🎲 GENERATING NEW HD WALLET".bright_cyan().bold());
+    println!("{}", "━".repeat(70).bright_blue());
+
+    let wallet = generate()?;
+
+    // Display security warning
+    display_security_warning();
+
+    if let Some(user_entropy) = &user_entropy {
+        display_user_entropy(user_entropy);
+    }
+
+    // Display mnemonic
+    println!("\n{}", "  🔑 BIP39 Mnemonic Seed Phrase:".bright_yellow().bold());
+    println!("{}", "  ━".repeat(35).yellow());
+
+    println!("  {} {}", "Language:".bold(), hd_wallet::language_name(wallet.mnemonic.language()));
+    for (i, word) in wallet.mnemonic.words().enumerate() {
+        if (i + 1) % 4 == 0 {
+            println!("  {:2}. {}", i + 1, word.bright_white().bold());
+        } else {
+            print!("  {:2}. {}    ", i + 1, word.bright_white().bold());
+        }
+    }
+    println!("\n{}", "  ━".repeat(35).yellow());
+
+    println!("\n  {} {}", "Master Seed (hex):".bold(), hex::encode(&wallet.seed[..32]).dimmed());
+    display_fingerprint(&wallet);
+
+    display_derivation_path(&wallet);
+
+    display_account_xpub(&wallet);
+
+    display_descriptors(&wallet);
+
+    display_addresses(&wallet, show_private);
+
+    Ok(())
+}
+
+/// Import and recover wallet from existing mnemonic
+pub fn run_import(args: ImportArgs, format: OutputFormat) -> Result<()> {
+    let indexes = args.keys.indexes();
+    let KeyArgs { passphrase, network, show_private_keys: show_private, .. } = args.keys;
+    let import = |mnemonic_str: &str, passphrase: &str| hd_wallet::import_wallet(
+        mnemonic_str, args.language.map(Into::into), args.path.as_deref(), args.script_type.map(Into::into), passphrase, network.into(),
+        indexes.clone(),
+    );
+
+    if !format.is_text() {
+        let mnemonic_str = args.mnemonic.read()?;
+        let passphrase = read_passphrase(passphrase)?;
+        return output::emit(format, &WalletOutput::new(&import(&mnemonic_str, &passphrase)?, false, show_private));
+    }
+
+    println!("\n{}", "━".repeat(70).bright_blue());
+    println!("{}", "  📥 IMPORTING WALLET FROM MNEMONIC".bright_cyan().bold());
+    println!("{}", "━".repeat(70).bright_blue());
+
+    let mnemonic_str = args.mnemonic.read()?;
+    let passphrase = read_passphrase(passphrase)?;
+
+    let wallet = import(&mnemonic_str, &passphrase)?;
+
+    println!("\n  {} {}", "✓".bright_green(), "Mnemonic validated successfully!".green());
+    println!("  {} {} words", "Word count:".bold(), wallet.mnemonic.word_count());
+    let detected = if args.language.is_some() { "" } else { " (detected)" };
+    println!("  {} {}{}", "Language:".bold(), hd_wallet::language_name(wallet.mnemonic.language()), detected.dimmed());
+    display_fingerprint(&wallet);
+
+    // Explain derivation path
+    display_derivation_path(&wallet);
+    if args.path.is_none() && args.script_type.is_none() {
+        println!("  {} Earlier releases imported from m/44'/0'/0'/0 (with bc1q addresses); for those addresses add",
+                 "ℹ".bright_blue());
+        println!("    --path \"m/44'/0'/0'/0\" --script-type p2wpkh");
+    }
+
+    display_account_xpub(&wallet);
+
+    display_descriptors(&wallet);
+
+    display_addresses(&wallet, show_private);
+
+    Ok(())
+}
+
+/// Derive receive and change addresses from an extended public key only
+pub fn run_derive_xpub(args: DeriveXpubArgs, format: OutputFormat) -> Result<()> {
+    let report = hd_wallet::watch_only_wallet(
+        &args.xpub, args.script_type.map(Into::into), args.network.map(Into::into),
+        args.from..args.from.saturating_add(args.count),
+    )?;
+
+    if !format.is_text() {
+        return output::emit(format, &WatchOnlyOutput::new(&args.xpub, &report));
+    }
+
+    println!("\n{}", "━".repeat(70).bright_blue());
+    println!("{}", "  🔭 WATCH-ONLY DERIVATION".bright_cyan().bold());
+    println!("{}", "━".repeat(70).bright_blue());
+
+    display_watch_only_key(&args.xpub, &report, args.script_type.is_some());
+    println!("\n{}", "  📊 RECEIVE (0/i) AND CHANGE (1/i) ADDRESSES:".bright_cyan().bold());
+    println!("{}", "  ━".repeat(35).cyan());
+    display_address_table(&report.receive, &report.change);
+    println!("{}", "  ━".repeat(35).cyan());
+
+    println!("\n  {} No private keys are involved - these addresses can be watched but not spent from",
+             "ℹ".bright_blue());
+    println!();
+    Ok(())
+}
+
+/// Accept only the mnemonic lengths BIP39 defines
+fn parse_word_count(value: &str) -> std::result::Result<usize, String> {
+    let word_count: usize = value.parse().map_err(|_| format!("'{}' is not a number", value))?;
+    if hd_wallet::WORD_COUNTS.contains(&word_count) {
+        Ok(word_count)
+    } else {
+        Err("BIP39 mnemonics have 12, 15, 18, 21 or 24 words".to_string())
+    }
+}
+
+impl MnemonicArgs {
+    /// Read the mnemonic from `--mnemonic`, `--mnemonic-file`, the
+    /// environment or a hidden prompt, in that order
+    pub(super) fn read(self) -> Result<String> {
+        let mnemonic = if let Some(mnemonic) = self.mnemonic {
+            eprintln!("{} --mnemonic leaves the phrase in shell history and visible to other users in the process list;",
+                      "⚠️  Warning:".bright_yellow().bold());
+            eprintln!("   prefer the hidden prompt (omit --mnemonic), --mnemonic-file or ${}", MNEMONIC_ENV);
+            mnemonic
+        } else if let Some(path) = self.mnemonic_file {
+            if path.as_os_str() == "-" {
+                std::io::read_to_string(std::io::stdin())?
+            } else {
+                std::fs::read_to_string(&path).with_context(|| format!("Cannot read mnemonic file {}", path.display()))?
+            }
+        } else if let Ok(mnemonic) = std::env::var(MNEMONIC_ENV) {
+            mnemonic
+        } else if std::io::stdin().is_terminal() {
+            dialoguer::Password::new()
+                .with_prompt("📝 Mnemonic (input hidden)")
+                .interact()?
+        } else {
+            std::io::read_to_string(std::io::stdin())?
+        };
+
+        // Files may hold one word per line
+        let mnemonic = mnemonic.split_whitespace().collect::<Vec<_>>().join(" ");
+        if mnemonic.is_empty() {
+            anyhow::bail!("No mnemonic given");
+        }
+        Ok(mnemonic)
+    }
+}
+
+/// Resolve the `--passphrase [VALUE]` flag: absent means no passphrase,
+/// and the bare flag prompts for one
+pub(super) fn read_passphrase(arg: Option<Option<String>>) -> Result<String> {
+    match arg {
+        None => Ok(String::new()),
+        Some(Some(passphrase)) => Ok(passphrase),
+        Some(None) => prompt_passphrase(),
+    }
+}
+
+/// Ask for a BIP39 passphrase without echoing it, typed twice
+fn prompt_passphrase() -> Result<String> {
+    let passphrase = dialoguer::Password::new()
+        .with_prompt("BIP39 passphrase")
+        .with_confirmation("Confirm passphrase", "Passphrases do not match")
+        .allow_empty_password(true)
+        .interact()
+        .context("Reading a passphrase needs an interactive terminal; pass --passphrase <PASSPHRASE> instead")?;
+    Ok(passphrase)
+}
+
+fn display_user_entropy(user_entropy: &UserEntropy) {
+    let inputs = match user_entropy.kind {
+        EntropyKind::Dice => format!("{} dice rolls, SHA-256", user_entropy.inputs),
+        EntropyKind::Coins => format!("{} coin flips", user_entropy.inputs),
+        EntropyKind::Hex => format!("{} bytes of hex", user_entropy.inputs),
+    };
+
+    println!("\n{}", "  🎲 User-Supplied Entropy:".bright_cyan().bold());
+    println!("{}", "  ━".repeat(35).cyan());
+    println!("  {} {} ({} bits)", "Source:".bold(), inputs.bright_white(), user_entropy.entropy.len() * 8);
+    println!("  {} {}", "Entropy (hex):".bold(), hex::encode(&user_entropy.entropy).dimmed());
+    if user_entropy.mixed_with_os_rng {
+        println!("  {} XORed with the OS RNG: at least as strong as either source, but the same input",
+                 "ℹ".bright_blue());
+        println!("    will not reproduce this mnemonic");
+    } else {
+        println!("  {} The same input always gives this mnemonic - destroy the record of it", "ℹ".bright_blue());
+    }
+    for warning in &user_entropy.warnings {
+        println!("  {} {}", "⚠".yellow(), warning.yellow());
+    }
+    println!("{}", "  ━".repeat(35).cyan());
+}
+
+fn display_fingerprint(wallet: &WalletReport) {
+    let note = if wallet.has_passphrase { "(with passphrase)" } else { "(no passphrase)" };
+    println!("  {} {} {}", "Master Fingerprint:".bold(),
+             wallet.master_fingerprint.to_string().bright_white().bold(), note.dimmed());
+    if wallet.has_passphrase {
+        println!("  {} A different passphrase silently opens a different wallet - check this fingerprint",
+                 "ℹ".bright_blue());
+    }
+}
+
+fn display_addresses(wallet: &WalletReport, show_private: bool) {
+    println!("\n{}", "  📊 DERIVED ADDRESSES:".bright_cyan().bold());
+    println!("{}", "  ━".repeat(35).cyan());
+    println!("  {} {}/i", "Receive:".bold(), wallet.derivation_path.to_string().dimmed());
+    match &wallet.change_path {
+        Some(path) => println!("  {} {}/i", "Change: ".bold(), path.to_string().dimmed()),
+        None => println!("  {} the path does not end in the external chain (…/0), so no change chain is shown",
+                         "ℹ".bright_blue()),
+    }
+    println!();
+    display_address_table(&wallet.addresses, &wallet.change_addresses);
+
+    if show_private {
+        let chains = [("receive", &wallet.addresses), ("change", &wallet.change_addresses)];
+        for (chain, derived) in chains.iter().flat_map(|(chain, list)| list.iter().map(move |d| (chain, d))) {
+            println!("\n  {} #{} ({})", "Address".bright_yellow().bold(), derived.index, chain);
+            println!("  {} {}", "Path:".bold(), derived.path.to_string().dimmed());
+            println!("  {} {}", "Address:".bold(), derived.address.to_string().bright_green());
+            println!("  {} {}", "Public Key:".bold(), derived.public_key.to_string().bright_white());
+            if let Some(private_key) = derived.private_key {
+                println!("  {} {}", "Private Key:".bold().red(), private_key.to_string().bright_red());
+                println!("  {} {}", "⚠️".yellow(), "KEEP THIS SECRET - NEVER SHARE!".red().bold());
+            }
+        }
+    } else {
+        println!("\n  {} Use --show-private-keys flag to display public and private keys (⚠️ DANGEROUS)",
+                 "ℹ".bright_blue());
+    }
+
+    println!("\n{}", "  ━".repeat(35).cyan());
+}
+
+/// Receive and change addresses side by side, marking where the BIP44 gap
+/// limit ends the scan of a freshly restored wallet
+fn display_address_table(receive: &[DerivedAddress], change: &[DerivedAddress]) {
+    let width = receive.iter().map(|derived| derived.address.to_string().len()).max().unwrap_or(0);
+
+    if receive.first().is_some_and(|first| first.index >= GAP_LIMIT) {
+        println!("  {} Every index here is beyond the gap limit of {}: a wallet restored from seed only",
+                 "⚠".yellow(), GAP_LIMIT);
+        println!("    finds them if no run of {} unused addresses comes before\n", GAP_LIMIT);
+    }
+    println!("  {} {:width$}  {}", format!("{:>6}", "index").dimmed(), "receive".dimmed(),
+             if change.is_empty() { "" } else { "change" }.dimmed(), width = width);
+
+    for (i, derived) in receive.iter().enumerate() {
+        if derived.index == GAP_LIMIT && i > 0 {
+            println!("  {}", format!("{:─^1$}", format!(" gap limit ({}) ", GAP_LIMIT), width * 2 + 9).yellow());
+        }
+        let change = change.get(i).map(|derived| derived.address.to_string()).unwrap_or_default();
+        println!("  {} {:width$}  {}", format!("{:>6}", derived.index).dimmed(),
+                 derived.address.to_string().bright_green(), change.green(), width = width);
+    }
+
+    if receive.iter().any(|derived| derived.index == GAP_LIMIT) {
+        println!("\n  {} Wallets restored from seed stop after {} unused addresses in a row; funds past",
+                 "ℹ".bright_blue(), GAP_LIMIT);
+        println!("    the marker are only found if an earlier address has been used");
+    }
+}
+
+fn display_account_xpub(wallet: &WalletReport) {
+    let encoded = slip132::encode(&wallet.account_xpub, wallet.script_type);
+
+    println!("\n{}", "  🔭 Account Extended Public Key (watch-only):".bright_cyan().bold());
+    println!("{}", "  ━".repeat(35).cyan());
+    println!("  {} {}", "Account Path:".bold(), wallet.account_path.to_string().bright_white());
+    println!("  {} {}", format!("{}:", slip132::prefix(&encoded)).bold(), encoded.bright_green());
+    if encoded != wallet.account_xpub.to_string() {
+        println!("  {} {}", format!("{}:", slip132::prefix(&wallet.account_xpub.to_string())).bold(),
+                 wallet.account_xpub.to_string().dimmed());
+    }
+    println!("  {} Shares every address but no private key; safe to give to accounting software",
+             "ℹ".bright_blue());
+
+    // Plain xpub/tpub keys do not say which script type they are for, and
+    // tpub keys do not say which test network
+    let mut flags = String::new();
+    if wallet.script_type == ScriptType::P2tr {
+        flags.push_str(" --script-type p2tr");
+    }
+    if matches!(wallet.network, Network::Signet | Network::Regtest) {
+        flags.push_str(&format!(" --network {}", utils::network_name(wallet.network)));
+    }
+    println!("  {} btc-tools derive-xpub {}{}", "Try:".dimmed(), encoded.dimmed(), flags.dimmed());
+    println!("{}", "  ━".repeat(35).cyan());
+}
+
+fn display_descriptors(wallet: &WalletReport) {
+    println!("\n{}", "  📜 Output Descriptors:".bright_cyan().bold());
+    println!("{}", "  ━".repeat(35).cyan());
+    for (label, descriptor) in ["Receive:", "Change:"].iter().zip(descriptor::wallet_descriptors(wallet)) {
+        println!("  {} {}", format!("{:8}", label).bold(), descriptor.to_string_with_checksum().bright_green());
+    }
+    println!("  {} Import these into a watch-only wallet (e.g. Bitcoin Core importdescriptors)",
+             "ℹ".bright_blue());
+    println!("{}", "  ━".repeat(35).cyan());
+}
+
+fn display_watch_only_key(encoded: &str, report: &WatchOnlyReport, explicit_script_type: bool) {
+    let prefix = slip132::prefix(encoded.trim());
+
+    println!("\n  {} {}", "Key Type:".bold(), prefix.bright_yellow());
+    println!("  {} {}", "Script Type:".bold(), report.script_type.to_string().bright_white());
+    println!("  {} {}", "Network:".bold(), utils::network_name(report.network).bright_white());
+    println!("  {} {} (parent fingerprint {})", "Depth:".bold(), report.xpub.depth,
+             report.xpub.parent_fingerprint);
+    if !explicit_script_type && matches!(prefix, "xpub" | "tpub") {
+        println!("\n  {} '{}' keys do not record a script type; assuming p2pkh (BIP44)", "!".yellow(), prefix);
+        println!("  {} Pass --script-type p2tr (BIP86) or another type if the addresses do not match",
+                 "ℹ".bright_blue());
+    }
+    if report.xpub.depth != 3 {
+        println!("\n  {} Account keys are normally at depth 3 (m/purpose'/coin'/account')", "!".yellow());
+    }
+}
+
+fn display_security_warning() {
+    println!("\n{}", "  ⚠️  SECURITY WARNING".bright_red().bold());
+    println!("{}", "  ━".repeat(35).red());
+    println!("  {} Write down this mnemonic on paper", "•".red());
+    println!("  {} NEVER store it digitally or take screenshots", "•".red());
+    println!("  {} Anyone with this phrase can access your funds", "•".red());
+    println!("  {} This is for EDUCATIONAL purposes only", "•".red());
+    println!("  {} Use hardware wallets for real Bitcoin", "•".red());
+    println!("{}", "  ━".repeat(35).red());
+}
+
+fn display_derivation_path(wallet: &WalletReport) {
+    let path = wallet.derivation_path.to_string();
+
+    println!("\n{}", "  🗺️  Derivation Path Explanation:".bright_cyan().bold());
+    println!("{}", "  ━".repeat(35).cyan());
+    println!("  {} {}", "Path:".bold(), path.bright_white());
+    println!("  {} {}", "Script Type:".bold(), wallet.script_type.to_string().bright_white());
+    println!("  {} {}", "Network:".bold(), utils::network_name(wallet.network).bright_white());
+    println!();
+
+    for (part, explanation) in hd_wallet::explain_derivation_path(&path) {
+        println!("  {} {}", format!("{:12}", part).bright_yellow(), explanation.white());
+    }
+
+    let expected_coin = hd_wallet::coin_type(wallet.network);
+    if let Some(&ChildNumber::Hardened { index: coin }) = wallet.derivation_path.as_ref().get(1) {
+        if coin != expected_coin {
+            println!("\n  {} Coin type {}' is unusual on {}; standard wallets use {}'",
+                     "⚠".yellow(), coin, utils::network_name(wallet.network), expected_coin);
+        }
+    }
+
+    if let Some(expected) = ScriptType::from_path(&wallet.derivation_path) {
+        if expected != wallet.script_type {
+            println!("\n  {} Purpose {}' normally uses {}; other wallets will not show these addresses",
+                     "⚠".yellow(), expected.purpose(), expected.to_string().bright_yellow());
+        }
+    }
+
+    println!("{}", "  ━".repeat(35).cyan());
+}
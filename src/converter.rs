@@ -1,226 +1,571 @@
-use anyhow::{Result, anyhow};
-use bitcoin::{Address, Network, PublicKey};
-use bitcoin::secp256k1::Secp256k1;
-use colored::*;
+use anyhow::{Result, anyhow, bail};
+use bitcoin::base58;
+use bitcoin::hashes::{sha256, Hash};
+use bitcoin::key::{Parity, TapTweak, TweakedPublicKey, XOnlyPublicKey};
+use bitcoin::secp256k1::{Secp256k1, SecretKey};
+use bitcoin::taproot::TapTweakHash;
+use bitcoin::script::PushBytesBuf;
+use bitcoin::{Address, AddressType, Network, PrivateKey, PublicKey, PubkeyHash, ScriptBuf, ScriptHash, WPubkeyHash,
+              WScriptHash, WitnessProgram, WitnessVersion};
+use std::fmt;
 use std::str::FromStr;
 
+use crate::validator::{self, AddressInfo};
+
+/// Outcome of converting a public key or address
+#[derive(Debug, Clone)]
+pub enum ConversionReport {
+    /// A public key was supplied: every address format it can produce
+    PublicKey {
+        network: Network,
+        public_key: PublicKey,
+        /// Legacy P2PKH, P2SH-wrapped SegWit, native SegWit and Taproot,
+        /// in that order
+        formats: Vec<Address>,
+    },
+    /// A private key was supplied: its public key and every address format,
+    /// plus the key itself for re-encoding
+    PrivateKey {
+        network: Network,
+        private_key: PrivateKey,
+        encoding: KeyEncoding,
+        public_key: PublicKey,
+        formats: Vec<Address>,
+    },
+    /// An address was supplied: addresses are one-way hashes, so only the
+    /// address itself can be described
+    Address {
+        network: Network,
+        address: Address,
+    },
+}
+
+/// How a private key was written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyEncoding {
+    /// Wallet Import Format: Base58Check with network and compression flag
+    Wif,
+    /// 32 raw bytes in hex, taken as compressed
+    Hex,
+}
+
+impl fmt::Display for KeyEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            KeyEncoding::Wif => "wif",
+            KeyEncoding::Hex => "hex",
+        })
+    }
+}
+
 /// Convert between different Bitcoin address formats
-pub fn convert_formats(input: &str, testnet: bool) -> Result<()> {
+pub fn convert_formats(input: &str, testnet: bool) -> Result<ConversionReport> {
     let network = if testnet { Network::Testnet } else { Network::Bitcoin };
 
-    println!("\n{}", "━".repeat(70).bright_blue());
-    println!("{}", "  🔄 ADDRESS FORMAT CONVERTER".bright_cyan().bold());
-    println!("  {} {}", "Network:".bold(), format!("{:?}", network).bright_yellow());
-    println!("{}", "━".repeat(70).bright_blue());
-    
     // Try to parse as public key first (hex format)
-    if let Ok(pubkey) = parse_public_key(input) {
-        display_all_formats_from_pubkey(&pubkey, network)?;
-    } 
+    if let Ok(public_key) = parse_public_key(input) {
+        let formats = address_formats(&public_key, network)?;
+        Ok(ConversionReport::PublicKey { network, public_key, formats })
+    }
+    // Then as a private key; a WIF key carries its own network
+    else if let Ok((private_key, encoding)) = parse_private_key(input) {
+        let network = match (encoding, testnet) {
+            (KeyEncoding::Wif, false) => private_key.network,
+            _ => network,
+        };
+        let private_key = PrivateKey { network, ..private_key };
+        let public_key = private_key.public_key(&Secp256k1::signing_only());
+        let formats = address_formats(&public_key, network)?;
+        Ok(ConversionReport::PrivateKey { network, private_key, encoding, public_key, formats })
+    }
     // Try to parse as address
     else if let Ok(address) = Address::from_str(input) {
-        let address_checked = address.assume_checked();
-        // Extract public key from address if possible
-        println!("\n  {} Input address detected: {}", "ℹ".bright_blue(), address_checked.to_string().bright_white());
-        println!("  {} Address type: {}", "ℹ".bright_blue(), 
-                 format!("{:?}", address_checked.address_type()).bright_yellow());
-        
-        // Note: We can't extract the public key from an address alone
-        // We can only show what formats would exist IF we had the pubkey
-        println!("\n  {} Cannot generate other formats from address alone", "⚠".yellow());
-        println!("  {} Addresses are one-way hashes of public keys", "ℹ".bright_blue());
-        println!("  {} To convert formats, please provide the public key (hex)", "💡".bright_yellow());
-        
-        display_address_info(&address_checked);
+        Ok(ConversionReport::Address { network, address: address.assume_checked() })
     }
     else {
-        return Err(anyhow!("Invalid input. Please provide:\n  • Public key in hex format (66 chars, compressed)\n  • Bitcoin address (any format)"));
+        Err(anyhow!("Invalid input. Please provide:\n  • Public key in hex format (66 chars, compressed)\n  • Private key in WIF or hex (64 chars)\n  • Bitcoin address (any format)"))
+    }
+}
+
+/// Parse a WIF private key, or 32 bytes of hex (optionally `0x`-prefixed)
+/// as a compressed mainnet key
+pub fn parse_private_key(input: &str) -> Result<(PrivateKey, KeyEncoding)> {
+    let input = input.trim();
+    if let Ok(private_key) = PrivateKey::from_wif(input) {
+        return Ok((private_key, KeyEncoding::Wif));
+    }
+
+    let bytes = hex::decode(input.trim_start_matches("0x"))
+        .map_err(|_| anyhow!("Not a WIF key or hex string"))?;
+    if bytes.len() != 32 {
+        bail!("A hex private key is 32 bytes, found {}", bytes.len());
     }
-    
-    Ok(())
+    let secret_key = SecretKey::from_slice(&bytes)
+        .map_err(|_| anyhow!("Private key is out of range (zero or not below the curve order)"))?;
+    Ok((PrivateKey::new(secret_key, Network::Bitcoin), KeyEncoding::Hex))
+}
+
+/// The key re-encoded for mainnet and for the test networks (testnet,
+/// signet and regtest share one WIF prefix); an uncompressed key is
+/// followed by its compressed form for each
+pub fn wif_encodings(private_key: &PrivateKey) -> Vec<PrivateKey> {
+    let compression = if private_key.compressed { vec![true] } else { vec![false, true] };
+    compression.into_iter()
+        .flat_map(|compressed| [Network::Bitcoin, Network::Testnet].map(|network| PrivateKey { network, compressed, ..*private_key }))
+        .collect()
 }
 
-fn parse_public_key(hex_str: &str) -> Result<PublicKey> {
+/// Parse a hex-encoded public key, tolerating whitespace and a `0x` prefix
+pub fn parse_public_key(hex_str: &str) -> Result<PublicKey> {
     // Remove any whitespace or 0x prefix
     let cleaned = hex_str.trim().trim_start_matches("0x");
-    
+
     // Decode hex
     let bytes = hex::decode(cleaned)
         .map_err(|_| anyhow!("Invalid hex string"))?;
-    
+
     // Parse as PublicKey
     PublicKey::from_slice(&bytes)
         .map_err(|e| anyhow!("Invalid public key: {}", e))
 }
 
-fn display_all_formats_from_pubkey(pubkey: &PublicKey, network: Network) -> Result<()> {
-    let _secp = Secp256k1::new();
-    
-    println!("\n  {} {}", "Public Key (compressed):".bold(), pubkey.to_string().bright_white());
-    println!();
-    
-    println!("{}", "  📋 ALL ADDRESS FORMATS:".bright_cyan().bold());
-    println!("{}", "  ━".repeat(35).cyan());
-    
+/// Every address format a public key can be encoded as on `network`
+///
+/// SegWit and Taproot need a compressed key, so an uncompressed one only
+/// has a P2PKH address.
+pub fn address_formats(pubkey: &PublicKey, network: Network) -> Result<Vec<Address>> {
     // 1. Legacy P2PKH
     let p2pkh_address = Address::p2pkh(pubkey, network);
-    let p2pkh_features = if network == Network::Testnet {
-        "• Starts with 'm' or 'n'\n  • For Testnet use only\n  • Base58Check encoding"
-    } else {
-        "• Starts with '1'\n  • Most compatible (all wallets support)\n  • Highest transaction fees\n  • Base58Check encoding"
-    };
-    display_format_box(
-        "1️⃣  Legacy (P2PKH)",
-        &p2pkh_address.to_string(),
-        p2pkh_features,
-        "🟡",
-    );
-    
+    if !pubkey.compressed {
+        return Ok(vec![p2pkh_address]);
+    }
+
     // 2. P2SH-SegWit (wrapped)
-    let wpubkey_hash = pubkey.wpubkey_hash()
-        .ok_or_else(|| anyhow!("Failed to generate wpubkey hash"))?;
-    let p2wpkh_script = bitcoin::script::Builder::new()
-        .push_slice(wpubkey_hash)
-        .into_script();
-    let p2sh_address = Address::p2sh(&p2wpkh_script, network)?;
-    let p2sh_features = if network == Network::Testnet {
-        "• Starts with '2'\n  • For Testnet use only\n  • Lower fees than legacy"
-    } else {
-        "• Starts with '3'\n  • Backward compatible with old wallets\n  • Lower fees than legacy (~25% savings)\n  • SegWit benefits in P2SH wrapper"
-    };
-    display_format_box(
-        "2️⃣  P2SH-SegWit (Wrapped)",
-        &p2sh_address.to_string(),
-        p2sh_features,
-        "🟢",
-    );
-    
+    let p2sh_address = Address::p2shwpkh(pubkey, network)?;
+
     // 3. Native SegWit (Bech32)
     let p2wpkh_address = Address::p2wpkh(pubkey, network)?;
-    let p2wpkh_features = if network == Network::Testnet {
-        "• Starts with 'tb1q'\n  • RECOMMENDED for Testnet\n  • Lowest fees\n  • Bech32 encoding"
-    } else {
-        "• Starts with 'bc1q'\n  • RECOMMENDED for new wallets\n  • ~40% lower fees than legacy\n  • Bech32 encoding (lowercase)\n  • Most efficient format"
-    };
-    display_format_box(
-        "3️⃣  Native SegWit (P2WPKH)",
-        &p2wpkh_address.to_string(),
-        p2wpkh_features,
-        "🟢",
-    );
-    
-    // 4. Taproot (if we can create it)
-    let taproot_prefix = if network == Network::Testnet { "tb1p" } else { "bc1p" };
-    println!("\n  {} Taproot (P2TR) addresses require key tweaking", "ℹ".bright_blue());
-    println!("    {} Cannot be directly derived from unmodified pubkey", "•".blue());
-    println!("    {} Would start with '{}'", "•".blue(), taproot_prefix);
-    println!("    {} Most advanced privacy and efficiency", "•".blue());
-    
-    // Fee comparison
-    if network == Network::Bitcoin {
-        display_fee_comparison();
-    }
-    
-    Ok(())
-}
-
-fn display_format_box(title: &str, address: &str, features: &str, icon: &str) {
-    println!("\n  {}", title.bright_yellow().bold());
-    println!("  {}", "─".repeat(35).dimmed());
-    println!("  {} {}", "Address:".bold(), address.bright_green());
-    println!("  {} Features:", "Info:".bold());
-    for line in features.lines() {
-        println!("  {}", line.white());
-    }
-    println!("  {}", icon);
-}
-
-fn display_fee_comparison() {
-    println!("\n{}", "  💰 TRANSACTION FEE COMPARISON (MAINNET):".bright_cyan().bold());
-    println!("{}", "  ━".repeat(40).cyan());
-    println!("  {} (Baseline: 100%)", "Legacy P2PKH:      ".bright_white());
-    println!("  {} (~75%)", "P2SH-SegWit:       ".bright_white());
-    println!("  {} (~60%) ✨ BEST", "Native SegWit:     ".bright_green().bold());
-    println!("  {} (~60%) 🔒 Most Private", "Taproot:           ".bright_green().bold());
-    println!();
-    println!("  {} Fee savings are approximate", "Note:".bold());
-    println!("  {} Actual savings depend on transaction complexity", "•".dimmed());
-    println!("  {} Native SegWit (bc1q) is recommended for most users", "•".dimmed());
-    println!("{}", "  ━".repeat(40).cyan());
-}
-
-fn display_address_info(address: &Address) {
-    println!("\n{}", "  📊 ADDRESS INFORMATION:".bright_cyan().bold());
-    println!("{}", "  ━".repeat(35).cyan());
-    
-    println!("  {} {}", "Network:".bold(), 
-             format!("{:?}", address.network()).bright_yellow());
-    
-    let script = address.script_pubkey();
-    println!("  {} {} bytes", "Script Size:".bold(), script.len());
-    
-    // Show what we know about this address type
-    match address.address_type() {
-        Some(bitcoin::AddressType::P2pkh) => {
-            println!("\n  {} This is a Legacy address", "Type:".bold());
-            println!("  {} Could be converted to SegWit for lower fees", "Tip:".bright_blue());
+
+    // 4. Taproot (Bech32m), key path only
+    let p2tr_address = Address::p2tr_tweaked(bip86_tweak(pubkey).output_key, network);
+
+    Ok(vec![p2pkh_address, p2sh_address, p2wpkh_address, p2tr_address])
+}
+
+/// How an address spells out its scriptPubKey
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AddressEncoding {
+    /// Legacy Base58Check: version byte, 20-byte hash, 4-byte checksum
+    Base58 {
+        version: u8,
+        /// HASH160 of the public key (P2PKH) or redeem script (P2SH)
+        hash: Vec<u8>,
+        /// First 4 bytes of SHA256d(version || hash)
+        checksum: [u8; 4],
+    },
+    /// SegWit: Bech32 for witness version 0, Bech32m from version 1 on
+    Bech32 {
+        hrp: String,
+        witness_version: u8,
+        /// Key or script hash (v0), or Taproot output key (v1)
+        program: Vec<u8>,
+        /// The last 6 characters, a BCH code over the rest
+        checksum: String,
+        bech32m: bool,
+    },
+}
+
+/// An address taken apart into its encoded fields and script
+#[derive(Debug, Clone)]
+pub struct AddressBreakdown {
+    pub info: AddressInfo,
+    pub encoding: AddressEncoding,
+    /// Electrum protocol script hash: SHA-256 of the scriptPubKey with its
+    /// bytes reversed, the key indexers look addresses up by
+    pub electrum_script_hash: [u8; 32],
+}
+
+/// Decode an address into its version or HRP, payload, checksum and script
+pub fn inspect_address(input: &str) -> Result<AddressBreakdown> {
+    let info = validator::validate_address(input, None)?;
+    let address = info.address.to_string();
+    let script = &info.script_pubkey;
+
+    let encoding = match script.witness_version() {
+        Some(version) => {
+            let separator = address.rfind('1').expect("bech32 addresses contain a separator");
+            AddressEncoding::Bech32 {
+                hrp: address[..separator].to_string(),
+                witness_version: version.to_num(),
+                program: script.as_bytes()[2..].to_vec(),
+                checksum: address[address.len() - 6..].to_string(),
+                bech32m: version.to_num() != 0,
+            }
         }
-        Some(bitcoin::AddressType::P2sh) => {
-            println!("\n  {} This is a P2SH address", "Type:".bold());
-            println!("  {} May contain SegWit or multisig script", "Note:".bright_blue());
+        None => {
+            let bytes = base58::decode(&address)?;
+            AddressEncoding::Base58 {
+                version: bytes[0],
+                hash: bytes[1..21].to_vec(),
+                checksum: bytes[21..25].try_into().expect("Base58Check addresses are 25 bytes"),
+            }
         }
-        Some(bitcoin::AddressType::P2wpkh) => {
-            println!("\n  {} This is a Native SegWit address", "Type:".bold());
-            println!("  {} Optimal for fees! 👍", "Status:".bright_green());
+    };
+
+    let mut electrum_script_hash = sha256::Hash::hash(script.as_bytes()).to_byte_array();
+    electrum_script_hash.reverse();
+    Ok(AddressBreakdown { info, encoding, electrum_script_hash })
+}
+
+/// Output types an address's hash can be re-encoded as
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressKind {
+    P2pkh,
+    P2shP2wpkh,
+    P2wpkh,
+    P2sh,
+    P2wsh,
+    P2shP2wsh,
+    P2tr,
+}
+
+impl fmt::Display for AddressKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            AddressKind::P2pkh => "p2pkh",
+            AddressKind::P2shP2wpkh => "p2sh-p2wpkh",
+            AddressKind::P2wpkh => "p2wpkh",
+            AddressKind::P2sh => "p2sh",
+            AddressKind::P2wsh => "p2wsh",
+            AddressKind::P2shP2wsh => "p2sh-p2wsh",
+            AddressKind::P2tr => "p2tr",
+        })
+    }
+}
+
+/// Networks with distinct address encodings (signet shares testnet's)
+pub const REENCODE_NETWORKS: [Network; 3] = [Network::Bitcoin, Network::Testnet, Network::Regtest];
+
+/// Risk of using a re-encoded address
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReencodeWarning {
+    /// A P2PKH hash may be of an uncompressed key, which SegWit outputs
+    /// cannot be spent with: coins sent there would be lost
+    RequiresCompressedKey,
+}
+
+impl fmt::Display for ReencodeWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            ReencodeWarning::RequiresCompressedKey => "requires_compressed_key",
+        })
+    }
+}
+
+/// One rendering of the input's hash
+#[derive(Debug, Clone)]
+pub struct Reencoding {
+    pub kind: AddressKind,
+    pub network: Network,
+    pub address: Address,
+    /// Whether this is the input address itself
+    pub is_input: bool,
+    pub warning: Option<ReencodeWarning>,
+}
+
+/// An address's hash rendered as other output types and networks
+#[derive(Debug, Clone)]
+pub struct ReencodeReport {
+    pub info: AddressInfo,
+    pub kind: AddressKind,
+    /// Key hash, script hash, or Taproot output key
+    pub hash: Vec<u8>,
+    pub reencodings: Vec<Reencoding>,
+}
+
+impl AddressKind {
+    /// Types that commit to the same hash as an address of this type
+    fn same_hash(self) -> &'static [AddressKind] {
+        match self {
+            AddressKind::P2pkh | AddressKind::P2shP2wpkh | AddressKind::P2wpkh =>
+                &[AddressKind::P2pkh, AddressKind::P2shP2wpkh, AddressKind::P2wpkh],
+            AddressKind::P2sh => &[AddressKind::P2sh],
+            AddressKind::P2wsh | AddressKind::P2shP2wsh => &[AddressKind::P2wsh, AddressKind::P2shP2wsh],
+            AddressKind::P2tr => &[AddressKind::P2tr],
         }
-        Some(bitcoin::AddressType::P2tr) => {
-            println!("\n  {} This is a Taproot address", "Type:".bold());
-            println!("  {} Most advanced format! 🚀", "Status:".bright_green());
+    }
+
+    /// Why other types cannot be derived from this one's hash
+    fn limitation(self) -> &'static str {
+        match self {
+            AddressKind::P2pkh | AddressKind::P2shP2wpkh | AddressKind::P2wpkh =>
+                "a public key hash only fits P2PKH, P2SH-P2WPKH and P2WPKH",
+            AddressKind::P2sh => "a P2SH hash commits to a script that is not revealed, so it only fits P2SH",
+            AddressKind::P2wsh | AddressKind::P2shP2wsh => "a witness script hash only fits P2WSH and P2SH-P2WSH",
+            AddressKind::P2tr => "a Taproot output key is not a hash and only fits P2TR",
         }
-        _ => {}
     }
-    
-    println!("{}", "  ━".repeat(35).cyan());
+
+    fn script_pubkey(self, hash: &[u8]) -> Result<ScriptBuf> {
+        Ok(match self {
+            AddressKind::P2pkh => ScriptBuf::new_p2pkh(&PubkeyHash::from_slice(hash)?),
+            AddressKind::P2wpkh => ScriptBuf::new_p2wpkh(&WPubkeyHash::from_slice(hash)?),
+            AddressKind::P2shP2wpkh => ScriptBuf::new_p2sh(&ScriptBuf::new_p2wpkh(&WPubkeyHash::from_slice(hash)?).script_hash()),
+            AddressKind::P2sh => ScriptBuf::new_p2sh(&ScriptHash::from_slice(hash)?),
+            AddressKind::P2wsh => ScriptBuf::new_p2wsh(&WScriptHash::from_slice(hash)?),
+            AddressKind::P2shP2wsh => ScriptBuf::new_p2sh(&ScriptBuf::new_p2wsh(&WScriptHash::from_slice(hash)?).script_hash()),
+            AddressKind::P2tr => ScriptBuf::new_witness_program(&WitnessProgram::new(WitnessVersion::V1, PushBytesBuf::try_from(hash.to_vec())?)?),
+        })
+    }
+}
+
+/// Render an address's hash as the other output types and networks it fits
+///
+/// `to` and `network` narrow the result; asking for a type the hash does
+/// not fit is an error. A P2PKH hash re-encoded as SegWit carries
+/// [`ReencodeWarning::RequiresCompressedKey`].
+pub fn reencode_address(input: &str, to: Option<AddressKind>, network: Option<Network>) -> Result<ReencodeReport> {
+    let info = validator::validate_address(input, None)?;
+    let script = &info.script_pubkey;
+    let (kind, hash) = match info.address_type {
+        Some(AddressType::P2pkh) => (AddressKind::P2pkh, &script.as_bytes()[3..23]),
+        Some(AddressType::P2sh) => (AddressKind::P2sh, &script.as_bytes()[2..22]),
+        Some(AddressType::P2wpkh) => (AddressKind::P2wpkh, &script.as_bytes()[2..]),
+        Some(AddressType::P2wsh) => (AddressKind::P2wsh, &script.as_bytes()[2..]),
+        Some(AddressType::P2tr) => (AddressKind::P2tr, &script.as_bytes()[2..]),
+        _ => bail!("{} addresses cannot be re-encoded", info.type_name()),
+    };
+
+    let kinds = match to {
+        Some(to) if !kind.same_hash().contains(&to) => bail!("A {} address cannot become {}: {}", kind, to, kind.limitation()),
+        Some(to) => vec![to],
+        None => kind.same_hash().to_vec(),
+    };
+    let networks = network.map_or_else(|| REENCODE_NETWORKS.to_vec(), |network| vec![network]);
+
+    let mut reencodings = Vec::new();
+    for &target in &kinds {
+        let target_script = target.script_pubkey(hash)?;
+        let warning = (kind == AddressKind::P2pkh && target != AddressKind::P2pkh)
+            .then_some(ReencodeWarning::RequiresCompressedKey);
+        for &network in &networks {
+            let address = Address::from_script(&target_script, network)?;
+            let is_input = target_script == *script && info.possible_networks.contains(&network);
+            reencodings.push(Reencoding { kind: target, network, address, is_input, warning });
+        }
+    }
+    Ok(ReencodeReport { kind, hash: hash.to_vec(), info, reencodings })
+}
+
+/// Compressed form of an uncompressed public key, with its addresses
+#[derive(Debug, Clone)]
+pub struct CompressedEquivalent {
+    pub public_key: PublicKey,
+    /// P2PKH, P2SH-wrapped SegWit, native SegWit and Taproot, as from
+    /// [`address_formats`]
+    pub formats: Vec<Address>,
+}
+
+/// The compressed form of `pubkey` and its addresses, if it is uncompressed
+///
+/// Both forms are the same point and spendable with the same private key,
+/// but they hash differently, so their P2PKH addresses differ: coins sent
+/// to one are not visible to a wallet watching the other.
+pub fn compressed_equivalent(pubkey: &PublicKey, network: Network) -> Result<Option<CompressedEquivalent>> {
+    if pubkey.compressed {
+        return Ok(None);
+    }
+    let public_key = PublicKey::new(pubkey.inner);
+    let formats = address_formats(&public_key, network)?;
+    Ok(Some(CompressedEquivalent { public_key, formats }))
+}
+
+/// BIP341 key tweak applied to a Taproot internal key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TaprootTweak {
+    /// The public key with its parity byte dropped
+    pub internal_key: XOnlyPublicKey,
+    /// `hashTapTweak(internal_key)`; BIP86 commits to no script tree
+    pub tweak: TapTweakHash,
+    /// `internal_key + tweak·G`, the key encoded in the `bc1p` address
+    pub output_key: TweakedPublicKey,
+    /// Whether the full output point has an odd Y coordinate
+    pub parity: Parity,
+}
+
+/// Tweak a public key into a key-path-only Taproot output key (BIP86)
+pub fn bip86_tweak(pubkey: &PublicKey) -> TaprootTweak {
+    let secp = Secp256k1::verification_only();
+    let internal_key = XOnlyPublicKey::from(pubkey.inner);
+    let tweak = TapTweakHash::from_key_and_tweak(internal_key, None);
+    let (output_key, parity) = internal_key.tap_tweak(&secp, None);
+
+    TaprootTweak {
+        internal_key,
+        tweak,
+        output_key,
+        parity,
+    }
 }
 
 /// Generate example public key for testing
 pub fn generate_example_pubkey() -> Result<PublicKey> {
     use bitcoin::secp256k1::Secp256k1;
     use rand::rngs::OsRng;
-    
+
     let secp = Secp256k1::new();
     let (_secret_key, public_key) = secp.generate_keypair(&mut OsRng);
-    
+
     Ok(PublicKey::new(public_key))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    
+
     #[test]
     fn test_example_conversion_mainnet() {
         // Test with a known public key
         let pubkey_hex = "02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5";
-        let result = convert_formats(pubkey_hex, false);
-        assert!(result.is_ok());
+        let report = convert_formats(pubkey_hex, false).unwrap();
+        match report {
+            ConversionReport::PublicKey { formats, .. } => {
+                let formats: Vec<String> = formats.iter().map(|a| a.to_string()).collect();
+                assert_eq!(formats[0], "1cMh228HTCiwS8ZsaakH8A8wze1JR5ZsP");
+                assert!(formats[1].starts_with('3'));
+                assert!(formats[2].starts_with("bc1q"));
+                assert!(formats[3].starts_with("bc1p"));
+            }
+            _ => panic!("expected a public key report"),
+        }
     }
 
     #[test]
     fn test_example_conversion_testnet() {
         let pubkey = generate_example_pubkey().unwrap();
-        let result_testnet = convert_formats(&pubkey.to_string(), true);
-        assert!(result_testnet.is_ok());
+        let report = convert_formats(&pubkey.to_string(), true).unwrap();
+        match report {
+            ConversionReport::PublicKey { network, formats, .. } => {
+                assert_eq!(network, Network::Testnet);
+                assert!(formats[2].to_string().starts_with("tb1q"));
+            }
+            _ => panic!("expected a public key report"),
+        }
     }
-    
+
+    #[test]
+    fn test_bip86_tweak() {
+        // BIP86 test vector: m/86'/0'/0'/0/0 of the "abandon ... about" mnemonic
+        let pubkey = parse_public_key("03cc8a4bc64d897bddc5fbc2f670f7a8ba0b386779106cf1223c6fc5d7cd6fc115").unwrap();
+        let tweak = bip86_tweak(&pubkey);
+        assert_eq!(tweak.output_key.to_string(), "a60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684c");
+
+        let formats = address_formats(&pubkey, Network::Bitcoin).unwrap();
+        assert_eq!(formats[3].to_string(), "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr");
+    }
+
     #[test]
     fn test_address_input() {
         let address = "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq";
         let result = convert_formats(address, false);
-        assert!(result.is_ok());
+        assert!(matches!(result, Ok(ConversionReport::Address { .. })));
 
         let testnet_address = "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7";
         let result_testnet = convert_formats(testnet_address, true);
         assert!(result_testnet.is_ok());
     }
+
+    #[test]
+    fn test_uncompressed_public_key() {
+        // The generator point G, uncompressed
+        let uncompressed = "0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8";
+        let ConversionReport::PublicKey { public_key, formats, .. } = convert_formats(uncompressed, false).unwrap() else {
+            panic!("expected a public key report");
+        };
+        assert_eq!(formats.len(), 1);
+        assert_eq!(formats[0].to_string(), "1EHNa6Q4Jz2uvNExL497mE43ikXhwF6kZm");
+
+        let equivalent = compressed_equivalent(&public_key, Network::Bitcoin).unwrap().unwrap();
+        assert_eq!(equivalent.public_key.to_string(), "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798");
+        assert_eq!(equivalent.formats[0].to_string(), "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH");
+        assert_eq!(equivalent.formats.len(), 4);
+
+        assert!(compressed_equivalent(&equivalent.public_key, Network::Bitcoin).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_private_key_input() {
+        // Secret key 1: its public key is the generator point G
+        let wif = "KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoWn";
+        let report = convert_formats(wif, false).unwrap();
+        let ConversionReport::PrivateKey { network, private_key, encoding, formats, .. } = report else {
+            panic!("expected a private key report");
+        };
+        assert_eq!((network, encoding), (Network::Bitcoin, KeyEncoding::Wif));
+        assert_eq!(formats[0].to_string(), "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH");
+        assert_eq!(wif_encodings(&private_key)[1].to_wif(), "cMahea7zqjxrtgAbB7LSGbcQUr1uX1ojuat9jZodMN87JcbXMTcA");
+
+        let hex_key = format!("{:064x}", 1);
+        let (private_key, encoding) = parse_private_key(&hex_key).unwrap();
+        assert_eq!((private_key.to_wif(), encoding), (wif.to_string(), KeyEncoding::Hex));
+
+        // Uncompressed WIF: only a P2PKH address
+        let report = convert_formats("5HpHagT65TZzG1PH3CSu63k8DbpvD8s5ip4nEB3kEsreAnchuDf", false).unwrap();
+        let ConversionReport::PrivateKey { formats, private_key, .. } = report else { panic!("expected a private key report") };
+        assert_eq!(formats.len(), 1);
+        assert_eq!(formats[0].to_string(), "1EHNa6Q4Jz2uvNExL497mE43ikXhwF6kZm");
+        assert_eq!(wif_encodings(&private_key)[2].to_wif(), wif);
+
+        assert!(parse_private_key(&"00".repeat(32)).is_err());
+    }
+
+    #[test]
+    fn test_inspect_address() {
+        // Electrum protocol documentation example
+        let breakdown = inspect_address("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa").unwrap();
+        let AddressEncoding::Base58 { version, hash, .. } = &breakdown.encoding else { panic!("expected Base58Check") };
+        assert_eq!((*version, hex::encode(hash).as_str()), (0, "62e907b15cbf27d5425399ebf6f0fb50ebb88f18"));
+        assert_eq!(hex::encode(breakdown.electrum_script_hash), "8b01df4e368ea28f8dc0423bcf7a4923e3a12d307c875e47a0cfbf90b5c39161");
+
+        // BIP173 example
+        let breakdown = inspect_address("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4").unwrap();
+        assert_eq!(breakdown.encoding, AddressEncoding::Bech32 {
+            hrp: "bc".to_string(),
+            witness_version: 0,
+            program: hex::decode("751e76e8199196d454941c45d1b3a323f1433bd6").unwrap(),
+            checksum: "v8f3t4".to_string(),
+            bech32m: false,
+        });
+        assert_eq!(breakdown.info.script_pubkey.to_asm_string(),
+                   "OP_0 OP_PUSHBYTES_20 751e76e8199196d454941c45d1b3a323f1433bd6");
+    }
+
+    #[test]
+    fn test_reencode_address() {
+        // Key hash 751e76e8... (BIP173) as P2PKH, re-encoded to SegWit and testnet
+        let report = reencode_address("1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH", None, None).unwrap();
+        assert_eq!(hex::encode(&report.hash), "751e76e8199196d454941c45d1b3a323f1433bd6");
+        let find = |kind, network| report.reencodings.iter().find(|r| r.kind == kind && r.network == network).unwrap();
+        assert!(find(AddressKind::P2pkh, Network::Bitcoin).is_input);
+        assert_eq!(find(AddressKind::P2pkh, Network::Testnet).address.to_string(), "mrCDrCybB6J1vRfbwM5hemdJz73FwDBC8r");
+        let p2wpkh = find(AddressKind::P2wpkh, Network::Bitcoin);
+        assert_eq!(p2wpkh.address.to_string(), "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+        assert_eq!(p2wpkh.warning, Some(ReencodeWarning::RequiresCompressedKey));
+
+        // SegWit key hashes are known to be of compressed keys
+        let report = reencode_address("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4", Some(AddressKind::P2pkh), Some(Network::Bitcoin)).unwrap();
+        assert_eq!(report.reencodings[0].address.to_string(), "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH");
+        assert_eq!(report.reencodings[0].warning, None);
+
+        // BIP173 P2WSH vector on mainnet and testnet
+        let report = reencode_address("bc1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3qccfmv3", Some(AddressKind::P2wsh), Some(Network::Testnet)).unwrap();
+        assert_eq!(report.reencodings[0].address.to_string(), "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7");
+
+        assert!(reencode_address("3JvL6Ymt8MVWiCNHC7oWU6nLeHNJKLZGLN", Some(AddressKind::P2wsh), None).is_err());
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(convert_formats("not a key", false).is_err());
+    }
 }
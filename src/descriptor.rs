@@ -0,0 +1,566 @@
+use anyhow::{Result, anyhow, bail};
+use bitcoin::bip32::{ChildNumber, DerivationPath, Fingerprint, Xpub};
+use bitcoin::blockdata::script::{Builder, ScriptBuf};
+use bitcoin::key::XOnlyPublicKey;
+use bitcoin::opcodes::all::OP_CHECKMULTISIG;
+use bitcoin::secp256k1::Secp256k1;
+use bitcoin::{Address, Network, PublicKey};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::hd_wallet::{ScriptType, WalletReport};
+use crate::utils;
+
+/// Characters allowed in a descriptor, in checksum symbol order (BIP380)
+const INPUT_CHARSET: &str =
+    "0123456789()[],'/*abcdefgh@:$%{}IJKLMNOPQRSTUVWXYZ&+-.;<=>?!^_|~ijklmnopqrstuvwxyzABCDEFGH`#\"\\ ";
+const CHECKSUM_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Largest multisig accepted inside `sh()`; P2SH redeem scripts are limited to 520 bytes
+const MAX_SH_MULTI_KEYS: usize = 15;
+/// Largest multisig accepted inside `wsh()`
+const MAX_WSH_MULTI_KEYS: usize = 20;
+
+/// An output descriptor (BIP380-386) this toolkit can derive addresses from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Descriptor {
+    /// `pkh(KEY)`
+    Pkh(DescriptorKey),
+    /// `wpkh(KEY)`
+    Wpkh(DescriptorKey),
+    /// `sh(wpkh(KEY))`
+    ShWpkh(DescriptorKey),
+    /// `tr(KEY)`, key path only
+    Tr(DescriptorKey),
+    /// `multi`/`sortedmulti` inside `sh()`, `wsh()` or `sh(wsh())`
+    Multi {
+        wrapper: MultiWrapper,
+        threshold: usize,
+        keys: Vec<DescriptorKey>,
+        /// `sortedmulti`: keys are sorted for each derived script
+        sorted: bool,
+    },
+}
+
+/// Script hash wrapper around a multisig script
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MultiWrapper {
+    Sh,
+    Wsh,
+    ShWsh,
+}
+
+/// A key expression, optionally with its origin: `[d34db33f/84'/0'/0']xpub.../0/*`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DescriptorKey {
+    /// Master fingerprint and path the key was derived with
+    pub origin: Option<(Fingerprint, DerivationPath)>,
+    pub key: KeySource,
+}
+
+/// Where a descriptor key comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeySource {
+    /// A fixed public key in hex
+    Single(PublicKey),
+    /// A 32-byte x-only key, only valid inside `tr()`
+    XOnly(XOnlyPublicKey),
+    /// An extended public key followed by unhardened steps and an optional `/*`
+    Extended {
+        xpub: Xpub,
+        path: DerivationPath,
+        wildcard: bool,
+    },
+}
+
+/// A parsed descriptor together with its checksum
+#[derive(Debug, Clone)]
+pub struct ParsedDescriptor {
+    pub descriptor: Descriptor,
+    /// Descriptor text as supplied, without the checksum
+    pub body: String,
+    /// Checksum computed over the input (the one supplied, if any, matched)
+    pub checksum: String,
+    /// Whether the input carried a `#checksum`
+    pub checksum_supplied: bool,
+}
+
+/// Parse a descriptor, verifying its checksum when one is appended
+pub fn parse_descriptor(input: &str) -> Result<ParsedDescriptor> {
+    let input = input.trim();
+    let (body, supplied) = match input.split_once('#') {
+        Some((body, checksum)) => (body, Some(checksum)),
+        None => (input, None),
+    };
+
+    let checksum = descriptor_checksum(body)?;
+    if let Some(supplied) = supplied {
+        if supplied != checksum {
+            bail!("Descriptor checksum mismatch: found #{}, expected #{}", supplied, checksum);
+        }
+    }
+
+    Ok(ParsedDescriptor {
+        descriptor: body.parse()?,
+        body: body.to_string(),
+        checksum,
+        checksum_supplied: supplied.is_some(),
+    })
+}
+
+impl ParsedDescriptor {
+    /// The descriptor as supplied, with its checksum appended
+    pub fn with_checksum(&self) -> String {
+        format!("{}#{}", self.body, self.checksum)
+    }
+}
+
+/// Compute the 8-character BIP380 checksum of a descriptor (without `#`)
+pub fn descriptor_checksum(descriptor: &str) -> Result<String> {
+    fn polymod(c: u64, value: u64) -> u64 {
+        const GENERATOR: [u64; 5] = [0xf5dee51989, 0xa9fdca3312, 0x1bab10e32d, 0x3706b1677a, 0x644d626ffd];
+        let top = c >> 35;
+        let mut c = ((c & 0x7ffffffff) << 5) ^ value;
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                c ^= generator;
+            }
+        }
+        c
+    }
+
+    let mut c = 1;
+    let mut class = 0;
+    let mut class_count = 0;
+    for ch in descriptor.chars() {
+        let position = INPUT_CHARSET.find(ch)
+            .ok_or_else(|| anyhow!("Invalid character '{}' in descriptor", ch))? as u64;
+        // Each symbol contributes its low 5 bits, and every 3 symbols their
+        // high bits as one extra value
+        c = polymod(c, position & 31);
+        class = class * 3 + (position >> 5);
+        class_count += 1;
+        if class_count == 3 {
+            c = polymod(c, class);
+            class = 0;
+            class_count = 0;
+        }
+    }
+    if class_count > 0 {
+        c = polymod(c, class);
+    }
+    for _ in 0..8 {
+        c = polymod(c, 0);
+    }
+    c ^= 1;
+
+    Ok((0..8).map(|i| CHECKSUM_CHARSET[((c >> (5 * (7 - i))) & 31) as usize] as char).collect())
+}
+
+impl Descriptor {
+    /// Descriptor for one chain of a standard single-key account, e.g.
+    /// `wpkh([fingerprint/84'/0'/0']xpub.../0/*)`
+    pub fn for_account(
+        script_type: ScriptType,
+        master_fingerprint: Fingerprint,
+        account_path: &DerivationPath,
+        account_xpub: Xpub,
+        chain: DerivationPath,
+    ) -> Self {
+        let key = DescriptorKey {
+            origin: Some((master_fingerprint, account_path.clone())),
+            key: KeySource::Extended { xpub: account_xpub, path: chain, wildcard: true },
+        };
+        match script_type {
+            ScriptType::P2pkh => Descriptor::Pkh(key),
+            ScriptType::P2shP2wpkh => Descriptor::ShWpkh(key),
+            ScriptType::P2wpkh => Descriptor::Wpkh(key),
+            ScriptType::P2tr => Descriptor::Tr(key),
+        }
+    }
+
+    /// Every key expression in the descriptor
+    pub fn keys(&self) -> &[DescriptorKey] {
+        match self {
+            Descriptor::Pkh(key) | Descriptor::Wpkh(key) | Descriptor::ShWpkh(key) | Descriptor::Tr(key) => {
+                std::slice::from_ref(key)
+            }
+            Descriptor::Multi { keys, .. } => keys,
+        }
+    }
+
+    /// Whether the descriptor describes a range of addresses (`/*`)
+    pub fn has_wildcard(&self) -> bool {
+        self.keys().iter().any(|key| matches!(key.key, KeySource::Extended { wildcard: true, .. }))
+    }
+
+    /// Network implied by the extended keys, if any: mainnet for `xpub`,
+    /// testnet for `tpub`
+    pub fn key_network(&self) -> Option<Network> {
+        self.keys().iter().find_map(|key| match &key.key {
+            KeySource::Extended { xpub, .. } => Some(xpub.network),
+            _ => None,
+        })
+    }
+
+    /// Short name of the output type, e.g. `p2wpkh` or `p2wsh multisig`
+    pub fn output_type(&self) -> &'static str {
+        match self {
+            Descriptor::Pkh(_) => "p2pkh",
+            Descriptor::Wpkh(_) => "p2wpkh",
+            Descriptor::ShWpkh(_) => "p2sh-p2wpkh",
+            Descriptor::Tr(_) => "p2tr",
+            Descriptor::Multi { wrapper: MultiWrapper::Sh, .. } => "p2sh multisig",
+            Descriptor::Multi { wrapper: MultiWrapper::Wsh, .. } => "p2wsh multisig",
+            Descriptor::Multi { wrapper: MultiWrapper::ShWsh, .. } => "p2sh-p2wsh multisig",
+        }
+    }
+
+    /// Canonical form followed by `#checksum`
+    pub fn to_string_with_checksum(&self) -> String {
+        let body = self.to_string();
+        let checksum = descriptor_checksum(&body).expect("descriptors only print valid characters");
+        format!("{}#{}", body, checksum)
+    }
+
+    /// Address at `index` of the range (ignored without a wildcard)
+    pub fn derive_address(&self, index: u32, network: Network) -> Result<Address> {
+        let secp = Secp256k1::verification_only();
+        let derive = |key: &DescriptorKey| key.derive(index);
+
+        Ok(match self {
+            Descriptor::Pkh(key) => Address::p2pkh(&derive(key)?, network),
+            Descriptor::Wpkh(key) => Address::p2wpkh(&derive(key)?, network)?,
+            Descriptor::ShWpkh(key) => Address::p2shwpkh(&derive(key)?, network)?,
+            Descriptor::Tr(key) => Address::p2tr(&secp, XOnlyPublicKey::from(derive(key)?.inner), None, network),
+            Descriptor::Multi { wrapper, threshold, keys, sorted } => {
+                let mut public_keys = keys.iter().map(derive).collect::<Result<Vec<_>>>()?;
+                if *sorted {
+                    public_keys.sort_by_key(|key| key.to_bytes());
+                }
+                let script = multisig_script(*threshold, &public_keys);
+                match wrapper {
+                    MultiWrapper::Sh => Address::p2sh(&script, network)?,
+                    MultiWrapper::Wsh => Address::p2wsh(&script, network),
+                    MultiWrapper::ShWsh => Address::p2shwsh(&script, network),
+                }
+            }
+        })
+    }
+}
+
+/// Output descriptors for a wallet's receive chain and, if it has one, its
+/// change chain
+pub fn wallet_descriptors(wallet: &WalletReport) -> Vec<Descriptor> {
+    std::iter::once(&wallet.derivation_path)
+        .chain(&wallet.change_path)
+        .map(|path| Descriptor::for_account(
+            wallet.script_type, wallet.master_fingerprint, &wallet.account_path, wallet.account_xpub,
+            DerivationPath::from(&path[wallet.account_path.len()..]),
+        ))
+        .collect()
+}
+
+/// `OP_k <key>... OP_n OP_CHECKMULTISIG`
+fn multisig_script(threshold: usize, keys: &[PublicKey]) -> ScriptBuf {
+    keys.iter()
+        .fold(Builder::new().push_int(threshold as i64), |builder, key| builder.push_key(key))
+        .push_int(keys.len() as i64)
+        .push_opcode(OP_CHECKMULTISIG)
+        .into_script()
+}
+
+impl DescriptorKey {
+    /// Public key at `index` (ignored without a wildcard)
+    pub fn derive(&self, index: u32) -> Result<PublicKey> {
+        match &self.key {
+            KeySource::Single(key) => Ok(*key),
+            KeySource::XOnly(key) => Ok(PublicKey::new(key.public_key(bitcoin::key::Parity::Even))),
+            KeySource::Extended { xpub, path, wildcard } => {
+                let secp = Secp256k1::verification_only();
+                let mut path = path.clone();
+                if *wildcard {
+                    path = path.child(ChildNumber::from_normal_idx(index)?);
+                }
+                Ok(PublicKey::new(xpub.derive_pub(&secp, &path)?.public_key))
+            }
+        }
+    }
+}
+
+impl FromStr for Descriptor {
+    type Err = anyhow::Error;
+
+    /// Parse a descriptor without its checksum; see [`parse_descriptor`]
+    fn from_str(s: &str) -> Result<Self> {
+        let (name, inner) = split_call(s)?;
+        let descriptor = match name {
+            "pkh" => Descriptor::Pkh(parse_key(inner, KeyContext::Legacy)?),
+            "wpkh" => Descriptor::Wpkh(parse_key(inner, KeyContext::Segwit)?),
+            "tr" => {
+                if inner.contains(',') {
+                    bail!("tr() with a script tree is not supported; only key-path tr(KEY) descriptors are");
+                }
+                Descriptor::Tr(parse_key(inner, KeyContext::Taproot)?)
+            }
+            "sh" => {
+                let (inner_name, inner_args) = split_call(inner)?;
+                match inner_name {
+                    "wpkh" => Descriptor::ShWpkh(parse_key(inner_args, KeyContext::Segwit)?),
+                    "wsh" => parse_multi(inner_args, MultiWrapper::ShWsh)?,
+                    "multi" | "sortedmulti" => parse_multi(inner, MultiWrapper::Sh)?,
+                    other => bail!("sh({}(...)) is not supported", other),
+                }
+            }
+            "wsh" => parse_multi(inner, MultiWrapper::Wsh)?,
+            "multi" | "sortedmulti" => bail!("Bare multisig has no address; wrap it in sh(), wsh() or sh(wsh())"),
+            "combo" | "raw" | "addr" | "rawtr" => bail!("{}() descriptors are not supported", name),
+            other => bail!("Unknown descriptor function '{}'", other),
+        };
+        Ok(descriptor)
+    }
+}
+
+/// Split `name(args)` into its name and arguments
+fn split_call(s: &str) -> Result<(&str, &str)> {
+    let open = s.find('(').ok_or_else(|| anyhow!("Expected name(...) in descriptor, found '{}'", s))?;
+    let inner = s[open + 1..].strip_suffix(')')
+        .ok_or_else(|| anyhow!("Unbalanced parentheses in descriptor '{}'", s))?;
+    Ok((&s[..open], inner))
+}
+
+/// Parse `multi(k,KEY,...)` or `sortedmulti(k,KEY,...)`
+fn parse_multi(s: &str, wrapper: MultiWrapper) -> Result<Descriptor> {
+    let (name, inner) = split_call(s)?;
+    let sorted = match name {
+        "multi" => false,
+        "sortedmulti" => true,
+        other => bail!("Expected multi() or sortedmulti() inside the script hash, found {}()", other),
+    };
+
+    let mut args = inner.split(',');
+    let threshold: usize = args.next().unwrap_or_default().parse()
+        .map_err(|_| anyhow!("The first argument of {}() must be the threshold", name))?;
+    let context = if wrapper == MultiWrapper::Sh { KeyContext::Legacy } else { KeyContext::Segwit };
+    let keys = args.map(|key| parse_key(key, context)).collect::<Result<Vec<_>>>()?;
+
+    let max_keys = if wrapper == MultiWrapper::Sh { MAX_SH_MULTI_KEYS } else { MAX_WSH_MULTI_KEYS };
+    if keys.is_empty() || keys.len() > max_keys {
+        bail!("{}() needs between 1 and {} keys here, found {}", name, max_keys, keys.len());
+    }
+    if threshold == 0 || threshold > keys.len() {
+        bail!("{}() threshold must be between 1 and {}, found {}", name, keys.len(), threshold);
+    }
+
+    Ok(Descriptor::Multi { wrapper, threshold, keys, sorted })
+}
+
+/// Which key encodings a script context allows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KeyContext {
+    /// Compressed or uncompressed keys
+    Legacy,
+    /// Compressed keys only
+    Segwit,
+    /// Compressed or x-only keys
+    Taproot,
+}
+
+/// Parse `[fingerprint/path]KEY/path/*`
+fn parse_key(s: &str, context: KeyContext) -> Result<DescriptorKey> {
+    let (origin, rest) = match s.strip_prefix('[') {
+        Some(after) => {
+            let (origin, rest) = after.split_once(']')
+                .ok_or_else(|| anyhow!("Unclosed key origin in '{}'", s))?;
+            let (fingerprint, path) = origin.split_once('/').unwrap_or((origin, ""));
+            let fingerprint = Fingerprint::from_str(fingerprint)
+                .map_err(|_| anyhow!("Key origin fingerprint must be 8 hex characters, found '{}'", fingerprint))?;
+            (Some((fingerprint, parse_path(path)?)), rest)
+        }
+        None => (None, s),
+    };
+
+    if rest.contains(['<', ';']) {
+        bail!("Multipath keys (<0;1>) are not supported; use one descriptor per chain, e.g. /0/* and /1/*");
+    }
+
+    let mut parts = rest.split('/');
+    let key_str = parts.next().unwrap_or_default();
+    let steps: Vec<&str> = parts.collect();
+
+    let key = if let Ok(xpub) = Xpub::from_str(key_str) {
+        let wildcard = match steps.last() {
+            Some(&"*") => true,
+            Some(step) if step.starts_with('*') => {
+                bail!("Hardened wildcards ({}) need the private key; use an unhardened /*", step)
+            }
+            _ => false,
+        };
+        let path = parse_path(&steps[..steps.len() - wildcard as usize].join("/"))?;
+        if path.into_iter().any(ChildNumber::is_hardened) {
+            bail!("Hardened steps after an xpub need the private key: {}", rest);
+        }
+        KeySource::Extended { xpub, path, wildcard }
+    } else if !steps.is_empty() {
+        bail!("Invalid extended public key '{}'", key_str);
+    } else if key_str.starts_with("xprv") || key_str.starts_with("tprv") {
+        bail!("Private keys are not accepted; use the matching xpub/tpub");
+    } else {
+        parse_hex_key(key_str, context)?
+    };
+
+    Ok(DescriptorKey { origin, key })
+}
+
+fn parse_hex_key(hex_str: &str, context: KeyContext) -> Result<KeySource> {
+    let bytes = hex::decode(hex_str).map_err(|_| anyhow!("Invalid key '{}': expected hex or an xpub", hex_str))?;
+    match (bytes.len(), context) {
+        (32, KeyContext::Taproot) => Ok(KeySource::XOnly(XOnlyPublicKey::from_slice(&bytes)?)),
+        (65, KeyContext::Segwit | KeyContext::Taproot) => {
+            bail!("Uncompressed keys are not allowed in SegWit or Taproot descriptors")
+        }
+        _ => Ok(KeySource::Single(PublicKey::from_slice(&bytes)
+            .map_err(|e| anyhow!("Invalid public key '{}': {}", hex_str, e))?)),
+    }
+}
+
+/// Parse path steps without the leading `m`, e.g. `84'/0'/0'`
+fn parse_path(steps: &str) -> Result<DerivationPath> {
+    if steps.is_empty() {
+        return Ok(DerivationPath::master());
+    }
+    // Descriptors also allow H as the hardened marker
+    format!("m/{}", steps.replace('H', "h")).parse()
+        .map_err(|e| anyhow!("Invalid derivation path '{}': {}", steps, e))
+}
+
+/// Derivation path steps without the leading `m`
+fn path_steps(path: &DerivationPath) -> String {
+    path.to_string().trim_start_matches('m').to_string()
+}
+
+impl fmt::Display for DescriptorKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some((fingerprint, path)) = &self.origin {
+            write!(f, "[{}{}]", fingerprint, path_steps(path))?;
+        }
+        match &self.key {
+            KeySource::Single(key) => write!(f, "{}", key),
+            KeySource::XOnly(key) => write!(f, "{}", key),
+            KeySource::Extended { xpub, path, wildcard } => {
+                write!(f, "{}{}", xpub, path_steps(path))?;
+                if *wildcard {
+                    f.write_str("/*")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl fmt::Display for Descriptor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Descriptor::Pkh(key) => write!(f, "pkh({})", key),
+            Descriptor::Wpkh(key) => write!(f, "wpkh({})", key),
+            Descriptor::ShWpkh(key) => write!(f, "sh(wpkh({}))", key),
+            Descriptor::Tr(key) => write!(f, "tr({})", key),
+            Descriptor::Multi { wrapper, threshold, keys, sorted } => {
+                let keys: Vec<String> = keys.iter().map(|key| key.to_string()).collect();
+                let multi = format!("{}({},{})", if *sorted { "sortedmulti" } else { "multi" }, threshold, keys.join(","));
+                match wrapper {
+                    MultiWrapper::Sh => write!(f, "sh({})", multi),
+                    MultiWrapper::Wsh => write!(f, "wsh({})", multi),
+                    MultiWrapper::ShWsh => write!(f, "sh(wsh({}))", multi),
+                }
+            }
+        }
+    }
+}
+
+/// Pick the network for a descriptor: `requested`, else the extended
+/// keys' network, else mainnet
+///
+/// `xpub` keys only work on mainnet; `tpub` keys on any test network.
+pub fn resolve_network(descriptor: &Descriptor, requested: Option<Network>) -> Result<Network> {
+    let mixed = descriptor.keys().iter().any(|key| matches!(&key.key,
+        KeySource::Extended { xpub, .. } if Some(xpub.network) != descriptor.key_network()));
+    if mixed {
+        bail!("The descriptor mixes xpub and tpub keys");
+    }
+
+    match (descriptor.key_network(), requested) {
+        (Some(key_network), Some(network)) if (key_network == Network::Bitcoin) != (network == Network::Bitcoin) => {
+            bail!("The descriptor's {} keys cannot derive {} addresses",
+                  if key_network == Network::Bitcoin { "xpub" } else { "tpub" }, utils::network_name(network))
+        }
+        (_, Some(network)) => Ok(network),
+        (Some(key_network), None) => Ok(key_network),
+        (None, None) => Ok(Network::Bitcoin),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hd_wallet;
+
+    #[test]
+    fn test_checksum() {
+        // BIP380 example
+        assert_eq!(descriptor_checksum("raw(deadbeef)").unwrap(), "89f8spxm");
+        assert!(parse_descriptor("pkh(02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5)#aaaaaaaa").is_err());
+    }
+
+    #[test]
+    fn test_single_key_vectors() {
+        // scriptPubKeys from the BIP381, BIP382 and BIP386 test vectors
+        let vectors = [
+            ("pkh(02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5)",
+             "76a91406afd46bcdfd22ef94ac122aa11f241244a37ecc88ac"),
+            ("wpkh(02f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9)",
+             "00147dd65592d0ab2fe0d0257d571abf032cd9db93dc"),
+            ("sh(wpkh(03fff97bd5755eeea420453a14355235d382f6472f8568a18b2f057a1460297556))",
+             "a914cc6ffbc0bf31af759451068f90ba7a0272b6b33287"),
+            ("tr(a34b99f22c790c4e36b2b3c2c35a36db06226e41c692fc82b8b56ac1c540c5bd)",
+             "512077aab6e066f8a7419c5ab714c12c67d25007ed55a43cadcacb4d7a970a093f11"),
+        ];
+        for (descriptor, script_pubkey) in vectors {
+            let parsed = parse_descriptor(descriptor).unwrap();
+            let address = parsed.descriptor.derive_address(0, Network::Bitcoin).unwrap();
+            assert_eq!(address.script_pubkey().to_hex_string(), script_pubkey, "{}", descriptor);
+            assert_eq!(parsed.descriptor.to_string(), descriptor);
+        }
+    }
+
+    #[test]
+    fn test_sortedmulti_ignores_key_order() {
+        let a = "03acd484e2f0c7f65309ad178a9f559abde09796974c57e714c35f110dfc27ccbe";
+        let b = "022f01e5e15cca351daff3843fb70f3c2f0a1bdd05e5af888a67784ef3e10a2a01";
+        let derive = |descriptor: String| parse_descriptor(&descriptor).unwrap()
+            .descriptor.derive_address(0, Network::Bitcoin).unwrap();
+
+        assert_eq!(derive(format!("wsh(sortedmulti(2,{},{}))", a, b)), derive(format!("wsh(sortedmulti(2,{},{}))", b, a)));
+        assert_eq!(derive(format!("wsh(sortedmulti(2,{},{}))", a, b)), derive(format!("wsh(multi(2,{},{}))", b, a)));
+        assert_ne!(derive(format!("wsh(multi(2,{},{}))", a, b)), derive(format!("wsh(multi(2,{},{}))", b, a)));
+        assert!(parse_descriptor(&format!("sh(multi(3,{},{}))", a, b)).is_err());
+    }
+
+    #[test]
+    fn test_wallet_descriptors_round_trip() {
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let wallet = hd_wallet::import_wallet(mnemonic, None, None, None, "", Network::Bitcoin, 0..2).unwrap();
+        let descriptors = wallet_descriptors(&wallet);
+        assert_eq!(descriptors.len(), 2);
+
+        let receive = descriptors[0].to_string_with_checksum();
+        assert!(receive.starts_with("wpkh([73c5da0a/84'/0'/0']xpub"));
+        assert!(receive.contains("/0/*)#"));
+
+        let parsed = parse_descriptor(&receive).unwrap();
+        assert!(parsed.checksum_supplied);
+        assert_eq!(parsed.descriptor.derive_address(1, Network::Bitcoin).unwrap(), wallet.addresses[1].address);
+        assert_eq!(descriptors[1].derive_address(0, Network::Bitcoin).unwrap().to_string(),
+                   "bc1q8c6fshw2dlwun7ekn9qwf37cu2rn755upcp6el");
+    }
+}
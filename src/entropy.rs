@@ -0,0 +1,224 @@
+use anyhow::{Result, anyhow, bail};
+use bitcoin::hashes::{sha256, Hash};
+use rand::RngCore;
+use std::fmt;
+
+use crate::hd_wallet;
+
+/// Chi-square critical values at p = 0.01: a fair die (5 degrees of
+/// freedom) or coin (1 degree) exceeds these only 1% of the time
+const DICE_CHI_SQUARE_LIMIT: f64 = 15.086;
+const COINS_CHI_SQUARE_LIMIT: f64 = 6.635;
+
+/// Physical or external randomness a mnemonic can be built from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntropyKind {
+    /// Six-sided dice rolls, hashed with SHA-256
+    Dice,
+    /// Coin flips, used directly as bits
+    Coins,
+    /// Raw entropy bytes in hex
+    Hex,
+}
+
+impl fmt::Display for EntropyKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            EntropyKind::Dice => "dice",
+            EntropyKind::Coins => "coins",
+            EntropyKind::Hex => "hex",
+        })
+    }
+}
+
+/// Mnemonic entropy built from user-supplied randomness
+#[derive(Debug, Clone)]
+pub struct UserEntropy {
+    pub kind: EntropyKind,
+    /// Entropy bytes for the mnemonic (16 to 32)
+    pub entropy: Vec<u8>,
+    /// Rolls, flips or bytes supplied
+    pub inputs: usize,
+    /// Signs the input may not be uniformly random
+    pub warnings: Vec<String>,
+    /// Whether the entropy was XORed with the operating system RNG
+    pub mixed_with_os_rng: bool,
+}
+
+/// Fewest d6 rolls that carry the entropy of a `word_count` mnemonic
+/// (log2 6 ≈ 2.58 bits per roll, so 50 rolls for 12 words, 100 for 24)
+pub fn min_dice_rolls(word_count: usize) -> Result<usize> {
+    let bits = hd_wallet::entropy_length(word_count)? * 8;
+    Ok((bits as f64 / 6f64.log2()).ceil() as usize)
+}
+
+/// Fewest coin flips for a `word_count` mnemonic: one per bit
+pub fn min_coin_flips(word_count: usize) -> Result<usize> {
+    Ok(hd_wallet::entropy_length(word_count)? * 8)
+}
+
+/// Entropy from six-sided dice rolls, digits 1-6 (whitespace is ignored)
+///
+/// The rolls are hashed with SHA-256 and the hash truncated to the
+/// mnemonic's entropy length, so the same rolls always give the same
+/// mnemonic and can be checked with any SHA-256 tool.
+pub fn from_dice(rolls: &str, word_count: usize) -> Result<UserEntropy> {
+    let rolls: String = rolls.chars().filter(|c| !c.is_whitespace()).collect();
+    if let Some(bad) = rolls.chars().find(|c| !('1'..='6').contains(c)) {
+        bail!("Dice rolls must be digits 1-6, found '{}'", bad);
+    }
+    let minimum = min_dice_rolls(word_count)?;
+    if rolls.len() < minimum {
+        bail!("{} words need at least {} dice rolls, found {}", word_count, minimum, rolls.len());
+    }
+
+    let mut counts = [0usize; 6];
+    for roll in rolls.bytes() {
+        counts[(roll - b'1') as usize] += 1;
+    }
+    let mut warnings = Vec::new();
+    if chi_square(&counts) > DICE_CHI_SQUARE_LIMIT {
+        warnings.push(format!(
+            "Faces 1-6 came up {:?} times; a fair die this uneven is unlikely - check for a biased die",
+            counts,
+        ));
+    }
+
+    let hash = sha256::Hash::hash(rolls.as_bytes());
+    Ok(UserEntropy {
+        kind: EntropyKind::Dice,
+        entropy: hash[..hd_wallet::entropy_length(word_count)?].to_vec(),
+        inputs: rolls.len(),
+        warnings,
+        mixed_with_os_rng: false,
+    })
+}
+
+/// Entropy from coin flips, `H`/`T` or `1`/`0` (whitespace is ignored)
+///
+/// Each flip is one bit, heads = 1, in the order given; flips beyond the
+/// mnemonic's entropy length are ignored.
+pub fn from_coins(flips: &str, word_count: usize) -> Result<UserEntropy> {
+    let bits = flips.chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| match c.to_ascii_uppercase() {
+            'H' | '1' => Ok(true),
+            'T' | '0' => Ok(false),
+            other => Err(anyhow!("Coin flips must be H/T or 1/0, found '{}'", other)),
+        })
+        .collect::<Result<Vec<bool>>>()?;
+    let needed = min_coin_flips(word_count)?;
+    if bits.len() < needed {
+        bail!("{} words need at least {} coin flips, found {}", word_count, needed, bits.len());
+    }
+
+    let mut warnings = Vec::new();
+    let heads = bits.iter().filter(|&&bit| bit).count();
+    if chi_square(&[heads, bits.len() - heads]) > COINS_CHI_SQUARE_LIMIT {
+        warnings.push(format!(
+            "{} heads in {} flips; a fair coin this uneven is unlikely - check for a biased coin or toss",
+            heads, bits.len(),
+        ));
+    }
+    if bits.len() > needed {
+        warnings.push(format!("Only the first {} of {} flips are used", needed, bits.len()));
+    }
+
+    let entropy = bits[..needed].chunks(8)
+        .map(|byte| byte.iter().fold(0u8, |acc, &bit| (acc << 1) | bit as u8))
+        .collect();
+    Ok(UserEntropy { kind: EntropyKind::Coins, entropy, inputs: bits.len(), warnings, mixed_with_os_rng: false })
+}
+
+/// Entropy given directly in hex; its length must match `word_count`
+pub fn from_hex(hex_str: &str, word_count: usize) -> Result<UserEntropy> {
+    let entropy = hex::decode(hex_str.trim()).map_err(|e| anyhow!("Invalid entropy hex: {}", e))?;
+    let needed = hd_wallet::entropy_length(word_count)?;
+    if entropy.len() != needed {
+        bail!("{} words need {} bytes ({} hex characters) of entropy, found {} bytes",
+              word_count, needed, needed * 2, entropy.len());
+    }
+
+    // Random bytes seldom repeat; this many duplicates suggests a pattern
+    let mut distinct = entropy.clone();
+    distinct.sort_unstable();
+    distinct.dedup();
+    let mut warnings = Vec::new();
+    if distinct.len() <= entropy.len() / 2 {
+        warnings.push(format!(
+            "Only {} distinct byte values in {} bytes; this does not look like random data",
+            distinct.len(), entropy.len(),
+        ));
+    }
+
+    Ok(UserEntropy { kind: EntropyKind::Hex, inputs: entropy.len(), entropy, warnings, mixed_with_os_rng: false })
+}
+
+impl UserEntropy {
+    /// XOR in the same number of bytes from the operating system RNG
+    ///
+    /// The result is at least as strong as the better of the two sources,
+    /// but it can no longer be reproduced from the rolls alone.
+    pub fn mix_os_rng(&mut self) {
+        let mut random = vec![0u8; self.entropy.len()];
+        rand::rngs::OsRng.fill_bytes(&mut random);
+        for (byte, random) in self.entropy.iter_mut().zip(random) {
+            *byte ^= random;
+        }
+        self.mixed_with_os_rng = true;
+    }
+}
+
+/// Pearson's chi-square statistic against equally likely outcomes
+fn chi_square(counts: &[usize]) -> f64 {
+    let expected = counts.iter().sum::<usize>() as f64 / counts.len() as f64;
+    counts.iter().map(|&count| (count as f64 - expected).powi(2) / expected).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dice() {
+        assert_eq!(min_dice_rolls(12).unwrap(), 50);
+        assert_eq!(min_dice_rolls(24).unwrap(), 100);
+
+        let rolls = "1234561234561234561234561234561234561234561234561234";
+        let entropy = from_dice(rolls, 12).unwrap();
+        assert_eq!(entropy.entropy, sha256::Hash::hash(rolls.as_bytes())[..16].to_vec());
+        assert!(entropy.warnings.is_empty());
+
+        assert!(from_dice(&rolls[..49], 12).is_err());
+        assert!(from_dice(&rolls.replace('6', "7"), 12).is_err());
+        assert_eq!(from_dice(&"1".repeat(50), 12).unwrap().warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_coins() {
+        let flips = "HT".repeat(64);
+        let entropy = from_coins(&flips, 12).unwrap();
+        assert_eq!(entropy.entropy, vec![0xaa; 16]);
+        assert!(entropy.warnings.is_empty());
+
+        assert!(from_coins(&flips[..127], 12).is_err());
+        assert!(!from_coins(&"H".repeat(128), 12).unwrap().warnings.is_empty());
+    }
+
+    #[test]
+    fn test_hex() {
+        // BIP39 vector: all-zero entropy is "abandon ... about"
+        let entropy = from_hex(&"00".repeat(16), 12).unwrap();
+        assert_eq!(entropy.warnings.len(), 1);
+        let wallet = hd_wallet::wallet_from_entropy(&entropy.entropy, bip39::Language::English, 0..1,
+            hd_wallet::DEFAULT_SCRIPT_TYPE, "", bitcoin::Network::Bitcoin).unwrap();
+        assert_eq!(wallet.master_fingerprint.to_string(), "73c5da0a");
+
+        assert!(from_hex(&"00".repeat(16), 24).is_err());
+
+        let mut mixed = entropy.clone();
+        mixed.mix_os_rng();
+        assert!(mixed.mixed_with_os_rng);
+        assert_ne!(mixed.entropy, entropy.entropy);
+    }
+}
@@ -1,216 +1,649 @@
 use anyhow::{Result, anyhow};
-use bitcoin::bip32::{DerivationPath, Xpriv, Xpub};
-use bitcoin::secp256k1::{Secp256k1, All};
-use bitcoin::{Address, Network};
+use bitcoin::bip32::{ChildNumber, DerivationPath, Fingerprint, Xpriv, Xpub};
+use bitcoin::key::XOnlyPublicKey;
+use bitcoin::secp256k1::{Secp256k1, Verification};
+use bitcoin::{Address, AddressType, Network, PublicKey};
 use bip39::{Mnemonic, Language};
-use colored::*;
-
-/// Generate a new HD wallet with BIP39 mnemonic
-pub fn generate_wallet(word_count: usize, address_count: usize, show_private: bool) -> Result<()> {
-    println!("\n{}", "━".repeat(70).bright_blue());
-    println!("{}", "  🎲 GENERATING NEW HD WALLET".bright_cyan().bold());
-    println!("{}", "━".repeat(70).bright_blue());
-    
-    // Validate word count and generate entropy
-    let entropy_length = match word_count {
-        12 => 16, // 128 bits
-        24 => 32, // 256 bits
-        _ => return Err(anyhow!("Word count must be 12 or 24")),
-    };
-    
-    // Generate random entropy
-    let mut entropy = vec![0u8; entropy_length];
-    use rand::RngCore;
-    rand::rngs::OsRng.fill_bytes(&mut entropy);
-    
-    // Generate new mnemonic
-    let mnemonic = Mnemonic::from_entropy_in(Language::English, &entropy)
-        .map_err(|e| anyhow!("Failed to generate mnemonic: {:?}", e))?;
-    let mnemonic_str: String = mnemonic.words().collect::<Vec<&str>>().join(" ");
-    
-    // Display security warning
-    display_security_warning();
-    
-    // Display mnemonic
-    println!("\n{}", "  🔑 BIP39 Mnemonic Seed Phrase:".bright_yellow().bold());
-    println!("{}", "  ━".repeat(35).yellow());
-    
-    let words: Vec<&str> = mnemonic_str.split_whitespace().collect();
-    for (i, word) in words.iter().enumerate() {
-        if (i + 1) % 4 == 0 {
-            println!("  {:2}. {}", i + 1, word.bright_white().bold());
-        } else {
-            print!("  {:2}. {}    ", i + 1, word.bright_white().bold());
+use std::borrow::Cow;
+use std::fmt;
+use std::ops::Range;
+
+use crate::{mnemonic_recovery, slip132, utils};
+
+/// Default derivation path used when none is supplied
+pub const DEFAULT_DERIVATION_PATH: &str = "m/84'/0'/0'/0";
+
+/// BIP44 address gap limit: wallets restoring from a seed stop scanning a
+/// chain after this many consecutive unused addresses
+pub const GAP_LIMIT: u32 = 20;
+
+/// Mnemonic lengths BIP39 defines: 128 to 256 bits of entropy in 32-bit steps
+pub const WORD_COUNTS: [usize; 5] = [12, 15, 18, 21, 24];
+
+/// Script type used when neither it nor a standard path is supplied
+pub const DEFAULT_SCRIPT_TYPE: ScriptType = ScriptType::P2wpkh;
+
+/// Address type a wallet's keys are encoded as
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScriptType {
+    /// Legacy P2PKH (BIP44)
+    P2pkh,
+    /// P2WPKH nested in P2SH (BIP49)
+    P2shP2wpkh,
+    /// Native SegWit P2WPKH (BIP84)
+    P2wpkh,
+    /// Key-path-only Taproot (BIP86)
+    P2tr,
+}
+
+impl ScriptType {
+    pub const ALL: [ScriptType; 4] = [ScriptType::P2pkh, ScriptType::P2shP2wpkh, ScriptType::P2wpkh, ScriptType::P2tr];
+
+    /// BIP43 purpose field of the standard path for this script type
+    pub fn purpose(self) -> u32 {
+        match self {
+            ScriptType::P2pkh => 44,
+            ScriptType::P2shP2wpkh => 49,
+            ScriptType::P2wpkh => 84,
+            ScriptType::P2tr => 86,
         }
     }
-    println!("\n{}", "  ━".repeat(35).yellow());
-    
-    // Generate seed from mnemonic
-    let seed = mnemonic.to_seed("");
-    println!("\n  {} {}", "Master Seed (hex):".bold(), hex::encode(&seed[..32]).dimmed());
-    
-    // Derive addresses
-    let secp = Secp256k1::new();
-    derive_and_display_addresses(&seed, &secp, address_count, show_private, "m/44'/0'/0'/0")?;
-    
-    Ok(())
+
+    /// Infer the script type from the purpose field of `path`, e.g. `m/49'/...`
+    pub fn from_path(path: &DerivationPath) -> Option<Self> {
+        match path.into_iter().next()? {
+            ChildNumber::Hardened { index } => Self::ALL.into_iter().find(|t| t.purpose() == *index),
+            ChildNumber::Normal { .. } => None,
+        }
+    }
+
+    /// Script type of a single-key address; P2SH is taken to be BIP49
+    /// nested SegWit, the only P2SH type a single-key wallet derives
+    pub fn from_address_type(address_type: AddressType) -> Option<Self> {
+        match address_type {
+            AddressType::P2pkh => Some(ScriptType::P2pkh),
+            AddressType::P2sh => Some(ScriptType::P2shP2wpkh),
+            AddressType::P2wpkh => Some(ScriptType::P2wpkh),
+            AddressType::P2tr => Some(ScriptType::P2tr),
+            _ => None,
+        }
+    }
+
+    /// Standard receive chain for the first account: `m/purpose'/coin_type'/0'/0`
+    pub fn default_path(self, network: Network) -> DerivationPath {
+        format!("m/{}'/{}'/0'/0", self.purpose(), coin_type(network)).parse().expect("valid derivation path")
+    }
+
+    /// Encode a public key as this script type
+    pub fn address<C: Verification>(self, secp: &Secp256k1<C>, public_key: &PublicKey, network: Network) -> Result<Address> {
+        Ok(match self {
+            ScriptType::P2pkh => Address::p2pkh(public_key, network),
+            ScriptType::P2shP2wpkh => Address::p2shwpkh(public_key, network)?,
+            ScriptType::P2wpkh => Address::p2wpkh(public_key, network)?,
+            ScriptType::P2tr => Address::p2tr(secp, XOnlyPublicKey::from(public_key.inner), None, network),
+        })
+    }
+}
+
+/// SLIP-44 coin type: `0` for mainnet, `1` for every test network
+pub fn coin_type(network: Network) -> u32 {
+    match network {
+        Network::Bitcoin => 0,
+        _ => 1,
+    }
+}
+
+impl fmt::Display for ScriptType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            ScriptType::P2pkh => "p2pkh",
+            ScriptType::P2shP2wpkh => "p2sh-p2wpkh",
+            ScriptType::P2wpkh => "p2wpkh",
+            ScriptType::P2tr => "p2tr",
+        })
+    }
+}
+
+/// A wallet generated or imported from a BIP39 mnemonic
+#[derive(Debug, Clone)]
+pub struct WalletReport {
+    /// BIP39 mnemonic the wallet was built from
+    pub mnemonic: Mnemonic,
+    /// 64-byte BIP39 seed
+    pub seed: [u8; 64],
+    /// Whether a BIP39 passphrase was mixed into the seed
+    pub has_passphrase: bool,
+    /// Fingerprint of the master key; changes with the passphrase, so it
+    /// confirms the passphrase was typed correctly without revealing it
+    pub master_fingerprint: Fingerprint,
+    /// Base path the addresses were derived under
+    pub derivation_path: DerivationPath,
+    /// Account level of the path: its hardened prefix, e.g. `m/84'/0'/0'`
+    pub account_path: DerivationPath,
+    /// Extended public key at `account_path`, for watch-only wallets
+    pub account_xpub: Xpub,
+    /// Address type the keys were encoded as
+    pub script_type: ScriptType,
+    /// Network of the extended keys and addresses
+    pub network: Network,
+    /// Derived child addresses, in index order
+    pub addresses: Vec<DerivedAddress>,
+    /// Internal chain matching `derivation_path` (`.../0` → `.../1`), if
+    /// the path ends in the standard external chain
+    pub change_path: Option<DerivationPath>,
+    /// Addresses at the same indexes on `change_path`
+    pub change_addresses: Vec<DerivedAddress>,
+}
+
+/// A single address derived from an HD wallet
+#[derive(Debug, Clone)]
+pub struct DerivedAddress {
+    /// Child index appended to the base path
+    pub index: u32,
+    /// Full derivation path of this key, or the path relative to the
+    /// extended public key for watch-only derivation
+    pub path: DerivationPath,
+    /// Address of the wallet's script type
+    pub address: Address,
+    /// Compressed public key
+    pub public_key: PublicKey,
+    /// Extended private key (⚠️ secret); `None` for watch-only derivation
+    pub private_key: Option<Xpriv>,
 }
 
-/// Import and recover wallet from existing mnemonic
+/// Addresses derived from an account extended public key alone
+#[derive(Debug, Clone)]
+pub struct WatchOnlyReport {
+    pub xpub: Xpub,
+    pub script_type: ScriptType,
+    pub network: Network,
+    /// External chain, `xpub/0/i`
+    pub receive: Vec<DerivedAddress>,
+    /// Internal chain, `xpub/1/i`
+    pub change: Vec<DerivedAddress>,
+}
+
+/// Generate a new HD wallet with a random BIP39 mnemonic
+///
+/// Addresses are derived from the standard account path for `script_type`
+/// on `network`. `passphrase` is the optional BIP39 passphrase
+/// ("25th word"); pass `""` for none.
+pub fn generate_wallet(
+    word_count: usize,
+    language: Language,
+    indexes: Range<u32>,
+    script_type: ScriptType,
+    passphrase: &str,
+    network: Network,
+) -> Result<WalletReport> {
+    let mnemonic = generate_mnemonic(word_count, language)?;
+    build_wallet(mnemonic, passphrase, script_type.default_path(network), script_type, network, indexes)
+}
+
+/// Build a new HD wallet from caller-supplied entropy, e.g. dice rolls
+///
+/// `entropy` must be 16, 20, 24, 28 or 32 bytes; its length sets the
+/// mnemonic length. Otherwise identical to [`generate_wallet`].
+pub fn wallet_from_entropy(
+    entropy: &[u8],
+    language: Language,
+    indexes: Range<u32>,
+    script_type: ScriptType,
+    passphrase: &str,
+    network: Network,
+) -> Result<WalletReport> {
+    let mnemonic = Mnemonic::from_entropy_in(language, entropy)
+        .map_err(|e| anyhow!("Invalid entropy: {}", e))?;
+    build_wallet(mnemonic, passphrase, script_type.default_path(network), script_type, network, indexes)
+}
+
+/// Import and recover a wallet from an existing mnemonic
+///
+/// The mnemonic's language is detected unless `language` is given. See
+/// [`resolve_path`] for how the path and script type are chosen.
 pub fn import_wallet(
-    mnemonic_str: Option<String>,
-    derivation_path: &str,
-    address_count: usize,
-    show_private: bool,
-) -> Result<()> {
-    println!("\n{}", "━".repeat(70).bright_blue());
-    println!("{}", "  📥 IMPORTING WALLET FROM MNEMONIC".bright_cyan().bold());
-    println!("{}", "━".repeat(70).bright_blue());
-    
-    // Get mnemonic from user if not provided
-    let mnemonic_str = match mnemonic_str {
-        Some(m) => m,
+    mnemonic_str: &str,
+    language: Option<Language>,
+    derivation_path: Option<&str>,
+    script_type: Option<ScriptType>,
+    passphrase: &str,
+    network: Network,
+    indexes: Range<u32>,
+) -> Result<WalletReport> {
+    let mnemonic = parse_mnemonic(mnemonic_str, language)?;
+
+    let (derivation_path, script_type) = resolve_path(derivation_path, script_type, network)?;
+    build_wallet(mnemonic, passphrase, derivation_path, script_type, network, indexes)
+}
+
+/// Fill in whichever of the base path and script type was not supplied
+///
+/// A path's purpose field (44/49/84/86) selects the matching script type,
+/// and a script type alone selects its standard path on `network`. An
+/// explicit script type always wins; paths with any other purpose default
+/// to P2WPKH.
+pub fn resolve_path(
+    derivation_path: Option<&str>,
+    script_type: Option<ScriptType>,
+    network: Network,
+) -> Result<(DerivationPath, ScriptType)> {
+    match derivation_path {
+        Some(path) => {
+            let path: DerivationPath = path.parse()
+                .map_err(|e| anyhow!("Invalid derivation path: {:?}", e))?;
+            let script_type = script_type
+                .or_else(|| ScriptType::from_path(&path))
+                .unwrap_or(DEFAULT_SCRIPT_TYPE);
+            Ok((path, script_type))
+        }
         None => {
-            println!("\n  {} Enter your BIP39 mnemonic phrase:", "📝".bold());
-            let mut input = String::new();
-            std::io::stdin().read_line(&mut input)?;
-            input.trim().to_string()
+            let script_type = script_type.unwrap_or(DEFAULT_SCRIPT_TYPE);
+            Ok((script_type.default_path(network), script_type))
         }
+    }
+}
+
+/// Parse and validate a BIP39 mnemonic, detecting its language if not given
+///
+/// Input is NFKD-normalized first, so precomposed characters (e.g. Japanese
+/// kana with dakuten) and the ideographic space U+3000 are accepted.
+pub fn parse_mnemonic(mnemonic_str: &str, language: Option<Language>) -> Result<Mnemonic> {
+    let mut normalized = Cow::Borrowed(mnemonic_str.trim());
+    Mnemonic::normalize_utf8_cow(&mut normalized);
+
+    let language = match language {
+        Some(language) => language,
+        None => Mnemonic::language_of(normalized.as_ref()).map_err(|e| match e {
+            bip39::Error::AmbiguousLanguages(ambiguous) => {
+                let names: Vec<&str> = ambiguous.iter().map(language_name).collect();
+                anyhow!("Mnemonic words fit several languages ({}); pass --language", names.join(", "))
+            }
+            bip39::Error::UnknownWord(i) => {
+                let word = normalized.split_whitespace().nth(i).unwrap_or_default();
+                anyhow!("Invalid mnemonic: word {} '{}' is not in any BIP39 word list{}",
+                        i + 1, word, did_you_mean(word, Language::English))
+            }
+            e => anyhow!("Invalid mnemonic: {}", e),
+        })?,
     };
-    
-    // Validate and parse mnemonic
-    let mnemonic = Mnemonic::parse_in_normalized(Language::English, &mnemonic_str)
-        .map_err(|e| anyhow!("Invalid mnemonic: {:?}", e))?;
-    
-    println!("\n  {} {}", "✓".bright_green(), "Mnemonic validated successfully!".green());
-    println!("  {} {} words", "Word count:".bold(), mnemonic_str.split_whitespace().count());
-    
-    // Generate seed
-    let seed = mnemonic.to_seed("");
-    
-    // Explain derivation path
-    explain_derivation_path(derivation_path);
-    
-    // Derive addresses
+
+    Mnemonic::parse_in_normalized(language, &normalized).map_err(|e| match e {
+        bip39::Error::UnknownWord(i) => {
+            let word = normalized.split_whitespace().nth(i).unwrap_or_default();
+            anyhow!("Invalid mnemonic: word {} '{}' is not in the {} word list{}",
+                    i + 1, word, language_name(language), did_you_mean(word, language))
+        }
+        bip39::Error::InvalidChecksum => {
+            anyhow!("Invalid mnemonic: checksum mismatch; recover-mnemonic can search for swapped or mistyped words")
+        }
+        e => anyhow!("Invalid mnemonic: {}", e),
+    })
+}
+
+/// " (did you mean ...?)" with the closest words, and a pointer to
+/// `recover-mnemonic`, for an unknown mnemonic word
+fn did_you_mean(word: &str, language: Language) -> String {
+    let suggestions = mnemonic_recovery::suggest_words(&word.to_lowercase(), language);
+    let mut hint = String::new();
+    if !suggestions.is_empty() {
+        hint = format!(" (did you mean {}?)", suggestions[..suggestions.len().min(3)].join(", "));
+    }
+    hint + "; recover-mnemonic can search for the intended phrase"
+}
+
+/// Mnemonic words as they should be written down
+///
+/// Japanese mnemonics are joined with the ideographic space U+3000, as
+/// BIP39 recommends; it normalizes back to a plain space for the seed.
+pub fn mnemonic_phrase(mnemonic: &Mnemonic) -> String {
+    let separator = if mnemonic.language() == Language::Japanese { "\u{3000}" } else { " " };
+    mnemonic.words().collect::<Vec<_>>().join(separator)
+}
+
+/// Lowercase name of a BIP39 word list, as accepted by `--language`
+pub fn language_name(language: Language) -> &'static str {
+    match language {
+        Language::English => "english",
+        Language::SimplifiedChinese => "chinese-simplified",
+        Language::TraditionalChinese => "chinese-traditional",
+        Language::Czech => "czech",
+        Language::French => "french",
+        Language::Italian => "italian",
+        Language::Japanese => "japanese",
+        Language::Korean => "korean",
+        Language::Portuguese => "portuguese",
+        Language::Spanish => "spanish",
+    }
+}
+
+/// Bytes of entropy behind a mnemonic of `word_count` words
+pub fn entropy_length(word_count: usize) -> Result<usize> {
+    // Every word holds 11 bits; one bit in 33 is checksum, so 3 words
+    // carry 32 bits of entropy (12 words → 128 bits ... 24 words → 256 bits)
+    if !WORD_COUNTS.contains(&word_count) {
+        return Err(anyhow!("Word count must be 12, 15, 18, 21 or 24"));
+    }
+    Ok(word_count / 3 * 4)
+}
+
+/// Generate a random BIP39 mnemonic of the given length and language
+pub fn generate_mnemonic(word_count: usize, language: Language) -> Result<Mnemonic> {
+    // Generate random entropy
+    let mut entropy = vec![0u8; entropy_length(word_count)?];
+    use rand::RngCore;
+    rand::rngs::OsRng.fill_bytes(&mut entropy);
+
+    Mnemonic::from_entropy_in(language, &entropy)
+        .map_err(|e| anyhow!("Failed to generate mnemonic: {:?}", e))
+}
+
+fn build_wallet(
+    mnemonic: Mnemonic,
+    passphrase: &str,
+    derivation_path: DerivationPath,
+    script_type: ScriptType,
+    network: Network,
+    indexes: Range<u32>,
+) -> Result<WalletReport> {
     let secp = Secp256k1::new();
-    derive_and_display_addresses(&seed, &secp, address_count, show_private, derivation_path)?;
-    
-    Ok(())
+
+    // Generate seed from mnemonic and passphrase
+    let seed = mnemonic.to_seed(passphrase);
+    let master_key = Xpriv::new_master(network, &seed)?;
+    let master_fingerprint = master_key.fingerprint(&secp);
+    let addresses = derive_addresses(&seed, &derivation_path, script_type, network, indexes.clone())?;
+
+    let account_path = account_path(&derivation_path);
+    let change_path = change_path(&derivation_path, &account_path);
+    let change_addresses = match &change_path {
+        Some(path) => derive_addresses(&seed, path, script_type, network, indexes)?,
+        None => Vec::new(),
+    };
+    let account_xpub = Xpub::from_priv(&secp, &master_key.derive_priv(&secp, &account_path)?);
+
+    Ok(WalletReport {
+        mnemonic,
+        seed,
+        has_passphrase: !passphrase.is_empty(),
+        master_fingerprint,
+        derivation_path,
+        account_path,
+        account_xpub,
+        script_type,
+        network,
+        addresses,
+        change_path,
+        change_addresses,
+    })
 }
 
-fn derive_and_display_addresses(
+/// Derive the child addresses of `script_type` at `indexes` under `base_path`
+///
+/// `network` selects the extended key version bytes (xprv/tprv) and the
+/// address encoding; the coin type is whatever `base_path` says.
+pub fn derive_addresses(
     seed: &[u8],
-    secp: &Secp256k1<All>,
-    count: usize,
-    show_private: bool,
-    base_path: &str,
-) -> Result<()> {
-    println!("\n{}", "  📊 DERIVED ADDRESSES:".bright_cyan().bold());
-    println!("{}", "  ━".repeat(35).cyan());
-    
-    // Parse base derivation path
-    let base_derivation: DerivationPath = base_path.parse()
-        .map_err(|e| anyhow!("Invalid derivation path: {:?}", e))?;
-    
-    // Create master key
-    let master_key = Xpriv::new_master(Network::Bitcoin, seed)?;
-    
-    // Derive addresses
-    for i in 0..count {
+    base_path: &DerivationPath,
+    script_type: ScriptType,
+    network: Network,
+    indexes: Range<u32>,
+) -> Result<Vec<DerivedAddress>> {
+    let secp = Secp256k1::new();
+
+    // Derive the base key once; each address is one step below it
+    let master_key = Xpriv::new_master(network, seed)?;
+    let base_key = master_key.derive_priv(&secp, base_path)?;
+
+    let mut addresses = Vec::with_capacity(indexes.len());
+    for i in indexes {
         // Create full path: base_path/i
-        let mut full_path = base_derivation.clone();
-        full_path = full_path.child(bitcoin::bip32::ChildNumber::from_normal_idx(i as u32)?);
-        
+        let child = ChildNumber::from_normal_idx(i)?;
+        let full_path = base_path.child(child);
+
         // Derive private key
-        let derived_private_key = master_key.derive_priv(secp, &full_path)?;
-        
+        let derived_private_key = base_key.derive_priv(&secp, &[child])?;
+
         // Get public key
-        let derived_public_key = Xpub::from_priv(secp, &derived_private_key);
-        let secp_pubkey = derived_public_key.public_key;
-        
-        // Convert secp256k1::PublicKey to bitcoin::PublicKey
-        let public_key = bitcoin::PublicKey::new(secp_pubkey);
-        
-        // Generate address (P2WPKH - Native SegWit)
-        let address = Address::p2wpkh(&public_key, Network::Bitcoin)
-            .expect("Failed to create address");
-        
-        // Display address info
-        println!("\n  {} #{}", "Address".bright_yellow().bold(), i);
-        println!("  {} {}", "Path:".bold(), full_path.to_string().dimmed());
-        println!("  {} {}", "Address:".bold(), address.to_string().bright_green());
-        println!("  {} {}", "Public Key:".bold(), public_key.to_string().bright_white());
-        
-        if show_private {
-            println!("  {} {}", "Private Key:".bold().red(), 
-                     derived_private_key.to_string().bright_red());
-            println!("  {} {}", "⚠️".yellow(), "KEEP THIS SECRET - NEVER SHARE!".red().bold());
-        }
+        let derived_public_key = Xpub::from_priv(&secp, &derived_private_key);
+        let public_key = PublicKey::new(derived_public_key.public_key);
+
+        let address = script_type.address(&secp, &public_key, network)?;
+
+        addresses.push(DerivedAddress {
+            index: i,
+            path: full_path,
+            address,
+            public_key,
+            private_key: Some(derived_private_key),
+        });
+    }
+
+    Ok(addresses)
+}
+
+/// Account level of a derivation path: everything up to its last hardened
+/// step, so `m/84'/0'/0'/0` gives `m/84'/0'/0'`
+pub fn account_path(path: &DerivationPath) -> DerivationPath {
+    let depth = path.into_iter().rposition(ChildNumber::is_hardened).map_or(0, |i| i + 1);
+    DerivationPath::from(&path[..depth])
+}
+
+/// Change chain for a base path ending in the external chain just below
+/// the account, so `m/84'/0'/0'/0` gives `m/84'/0'/0'/1`
+pub fn change_path(base_path: &DerivationPath, account_path: &DerivationPath) -> Option<DerivationPath> {
+    (base_path[account_path.len()..] == [ChildNumber::Normal { index: 0 }])
+        .then(|| account_path.child(ChildNumber::Normal { index: 1 }))
+}
+
+/// Derive receive and change addresses from an extended public key
+///
+/// Accepts any SLIP-132 encoding. The script type comes from `script_type`,
+/// else the key's prefix (`ypub` → P2SH-P2WPKH, `zpub` → P2WPKH), else
+/// P2PKH as SLIP-132 specifies for `xpub`. Test network keys (`tpub`, ...)
+/// default to testnet but may be used on signet or regtest.
+pub fn watch_only_wallet(
+    encoded_xpub: &str,
+    script_type: Option<ScriptType>,
+    network: Option<Network>,
+    indexes: Range<u32>,
+) -> Result<WatchOnlyReport> {
+    let (xpub, implied) = slip132::decode(encoded_xpub)?;
+    let script_type = script_type.or(implied).unwrap_or(ScriptType::P2pkh);
+
+    let key_is_mainnet = xpub.network == Network::Bitcoin;
+    let network = network.unwrap_or(xpub.network);
+    if key_is_mainnet != (network == Network::Bitcoin) {
+        return Err(anyhow!("A {} key cannot derive {} addresses",
+                           slip132::prefix(encoded_xpub.trim()), utils::network_name(network)));
     }
-    
-    if !show_private {
-        println!("\n  {} Use --show-private-keys flag to display private keys (⚠️ DANGEROUS)", 
-                 "ℹ".bright_blue());
-    }
-    
-    println!("\n{}", "  ━".repeat(35).cyan());
-    
-    Ok(())
-}
-
-fn display_security_warning() {
-    println!("\n{}", "  ⚠️  SECURITY WARNING".bright_red().bold());
-    println!("{}", "  ━".repeat(35).red());
-    println!("  {} Write down this mnemonic on paper", "•".red());
-    println!("  {} NEVER store it digitally or take screenshots", "•".red());
-    println!("  {} Anyone with this phrase can access your funds", "•".red());
-    println!("  {} This is for EDUCATIONAL purposes only", "•".red());
-    println!("  {} Use hardware wallets for real Bitcoin", "•".red());
-    println!("{}", "  ━".repeat(35).red());
-}
-
-fn explain_derivation_path(path: &str) {
-    println!("\n{}", "  🗺️  Derivation Path Explanation:".bright_cyan().bold());
-    println!("{}", "  ━".repeat(35).cyan());
-    println!("  {} {}", "Path:".bold(), path.bright_white());
-    println!();
-    
-    // Parse and explain each part
-    let parts: Vec<&str> = path.split('/').collect();
-    
-    for (i, part) in parts.iter().enumerate() {
-        let explanation = match (i, part.trim_end_matches('\'')) {
-            (0, "m") => "m = Master key (root of the tree)",
-            (1, "44") => "44' = Purpose (BIP44 - Multi-Account Hierarchy)",
-            (2, "0") => "0' = Coin type (0 = Bitcoin, 1 = Testnet)",
-            (2, "1") => "1' = Coin type (1 = Bitcoin Testnet)",
-            (3, _) => "Account number (0' = first account)",
-            (4, "0") => "0 = External chain (receiving addresses)",
-            (4, "1") => "1 = Internal chain (change addresses)",
-            (5, _) => "Address index (increments for each address)",
-            _ => "Unknown component",
-        };
-        
-        println!("  {} {}", format!("{:12}", part).bright_yellow(), explanation.white());
-    }
-    
-    println!("{}", "  ━".repeat(35).cyan());
+
+    Ok(WatchOnlyReport {
+        receive: derive_public_addresses(&xpub, 0, script_type, network, indexes.clone())?,
+        change: derive_public_addresses(&xpub, 1, script_type, network, indexes)?,
+        xpub,
+        script_type,
+        network,
+    })
+}
+
+/// Derive the addresses at `indexes` on `chain` below an extended public key
+pub fn derive_public_addresses(
+    xpub: &Xpub,
+    chain: u32,
+    script_type: ScriptType,
+    network: Network,
+    indexes: Range<u32>,
+) -> Result<Vec<DerivedAddress>> {
+    let secp = Secp256k1::verification_only();
+    let chain_path = DerivationPath::master().child(ChildNumber::from_normal_idx(chain)?);
+    let chain_key = xpub.derive_pub(&secp, &chain_path)?;
+
+    indexes
+        .map(|i| {
+            let child = ChildNumber::from_normal_idx(i)?;
+            let public_key = PublicKey::new(chain_key.ckd_pub(&secp, child)?.public_key);
+            Ok(DerivedAddress {
+                index: i,
+                path: chain_path.child(child),
+                address: script_type.address(&secp, &public_key, network)?,
+                public_key,
+                private_key: None,
+            })
+        })
+        .collect()
+}
+
+/// Explain each component of a derivation path, e.g. `44'` → purpose
+pub fn explain_derivation_path(path: &str) -> Vec<(String, &'static str)> {
+    path.split('/')
+        .enumerate()
+        .map(|(i, part)| {
+            let explanation = match (i, part.trim_end_matches('\'')) {
+                (0, "m") => "m = Master key (root of the tree)",
+                (1, "44") => "44' = Purpose (BIP44 - Legacy P2PKH)",
+                (1, "49") => "49' = Purpose (BIP49 - P2WPKH nested in P2SH)",
+                (1, "84") => "84' = Purpose (BIP84 - Native SegWit P2WPKH)",
+                (1, "86") => "86' = Purpose (BIP86 - Taproot P2TR)",
+                (2, "0") => "0' = Coin type (0 = Bitcoin, 1 = Testnet)",
+                (2, "1") => "1' = Coin type (1 = Bitcoin Testnet)",
+                (3, _) => "Account number (0' = first account)",
+                (4, "0") => "0 = External chain (receiving addresses)",
+                (4, "1") => "1 = Internal chain (change addresses)",
+                (5, _) => "Address index (increments for each address)",
+                _ => "Unknown component",
+            };
+            (part.to_string(), explanation)
+        })
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    
+
+    const TEST_MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
     #[test]
     fn test_generate_wallet() {
-        let result = generate_wallet(12, 3, false);
-        assert!(result.is_ok());
+        let wallet = generate_wallet(12, Language::English, 0..3, DEFAULT_SCRIPT_TYPE, "", Network::Bitcoin).unwrap();
+        assert_eq!(wallet.mnemonic.word_count(), 12);
+        assert_eq!(wallet.addresses.len(), 3);
+        assert_eq!(wallet.derivation_path.to_string(), DEFAULT_DERIVATION_PATH);
     }
-    
+
+    #[test]
+    fn test_word_counts() {
+        for word_count in WORD_COUNTS {
+            let mnemonic = generate_mnemonic(word_count, Language::English).unwrap();
+            assert_eq!(mnemonic.word_count(), word_count);
+            assert_eq!(mnemonic.to_entropy().len() * 8, word_count / 3 * 32);
+        }
+        assert!(generate_mnemonic(13, Language::English).is_err());
+    }
+
     #[test]
     fn test_valid_mnemonic_import() {
-        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
-        let result = import_wallet(Some(mnemonic.to_string()), "m/44'/0'/0'/0", 2, false);
-        assert!(result.is_ok());
+        let wallet = import_wallet(TEST_MNEMONIC, None, Some("m/44'/0'/0'/0"), None, "", Network::Bitcoin, 0..2).unwrap();
+        assert_eq!(wallet.addresses.len(), 2);
+        assert_eq!(wallet.addresses[1].path.to_string(), "m/44'/0'/0'/0/1");
+    }
+
+    #[test]
+    fn test_purpose_selects_script_type() {
+        // First receive address of each standard account (BIP44/49/84/86 test vectors)
+        let expected = [
+            ("m/44'/0'/0'/0", "1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA"),
+            ("m/49'/0'/0'/0", "37VucYSaXLCAsxYyAPfbSi9eh4iEcbShgf"),
+            ("m/84'/0'/0'/0", "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu"),
+            ("m/86'/0'/0'/0", "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr"),
+        ];
+        for (path, address) in expected {
+            let wallet = import_wallet(TEST_MNEMONIC, None, Some(path), None, "", Network::Bitcoin, 0..1).unwrap();
+            assert_eq!(wallet.addresses[0].address.to_string(), address, "{}", path);
+        }
+
+        // A script type alone selects its standard account path
+        let wallet = import_wallet(TEST_MNEMONIC, None, None, Some(ScriptType::P2tr), "", Network::Bitcoin, 0..1).unwrap();
+        assert_eq!(wallet.derivation_path.to_string(), "m/86'/0'/0'/0");
+    }
+
+    #[test]
+    fn test_passphrase_changes_seed() {
+        let plain = import_wallet(TEST_MNEMONIC, None, None, None, "", Network::Bitcoin, 0..1).unwrap();
+        assert_eq!(plain.master_fingerprint.to_string(), "73c5da0a");
+        assert!(!plain.has_passphrase);
+
+        // BIP39 reference vector for the passphrase "TREZOR"
+        let protected = import_wallet(TEST_MNEMONIC, None, None, None, "TREZOR", Network::Bitcoin, 0..1).unwrap();
+        assert_eq!(hex::encode(protected.seed), "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04");
+        assert_ne!(protected.master_fingerprint, plain.master_fingerprint);
+        assert_ne!(protected.addresses[0].address, plain.addresses[0].address);
+    }
+
+    #[test]
+    fn test_test_networks() {
+        // BIP84 test vector for the first testnet receive address
+        let wallet = import_wallet(TEST_MNEMONIC, None, None, None, "", Network::Testnet, 0..1).unwrap();
+        assert_eq!(wallet.derivation_path.to_string(), "m/84'/1'/0'/0");
+        assert_eq!(wallet.addresses[0].address.to_string(), "tb1q6rz28mcfaxtmd6v789l9rrlrusdprr9pqcpvkl");
+        assert!(wallet.addresses[0].private_key.unwrap().to_string().starts_with("tprv"));
+
+        let regtest = import_wallet(TEST_MNEMONIC, None, None, None, "", Network::Regtest, 0..1).unwrap();
+        assert!(regtest.addresses[0].address.to_string().starts_with("bcrt1q"));
+        assert_eq!(regtest.addresses[0].public_key, wallet.addresses[0].public_key);
+    }
+
+    #[test]
+    fn test_account_xpub_watch_only() {
+        let wallet = import_wallet(TEST_MNEMONIC, None, None, None, "", Network::Bitcoin, 0..2).unwrap();
+        assert_eq!(wallet.account_path.to_string(), "m/84'/0'/0'");
+        let zpub = slip132::encode(&wallet.account_xpub, wallet.script_type);
+        assert_eq!(zpub, "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs");
+
+        // The watch-only receive chain matches the wallet; change is BIP84's m/84'/0'/0'/1/0
+        let watch_only = watch_only_wallet(&zpub, None, None, 0..2).unwrap();
+        assert_eq!(watch_only.script_type, ScriptType::P2wpkh);
+        assert_eq!(watch_only.receive[1].address, wallet.addresses[1].address);
+        assert_eq!(watch_only.change[0].address.to_string(), "bc1q8c6fshw2dlwun7ekn9qwf37cu2rn755upcp6el");
+        assert!(watch_only_wallet(&zpub, None, Some(Network::Testnet), 0..1).is_err());
+    }
+
+    #[test]
+    fn test_change_chain_and_range() {
+        let wallet = import_wallet(TEST_MNEMONIC, None, None, None, "", Network::Bitcoin, 1000..1002).unwrap();
+        assert_eq!(wallet.addresses[0].path.to_string(), "m/84'/0'/0'/0/1000");
+        assert_eq!(wallet.change_path.as_ref().unwrap().to_string(), "m/84'/0'/0'/1");
+        assert_eq!(wallet.change_addresses[1].path.to_string(), "m/84'/0'/0'/1/1001");
+
+        let first = import_wallet(TEST_MNEMONIC, None, None, None, "", Network::Bitcoin, 0..1).unwrap();
+        assert_eq!(first.change_addresses[0].address.to_string(), "bc1q8c6fshw2dlwun7ekn9qwf37cu2rn755upcp6el");
+
+        // Paths that do not end in the external chain have no change chain
+        let custom = import_wallet(TEST_MNEMONIC, None, Some("m/84'/0'/0'/5"), None, "", Network::Bitcoin, 0..1).unwrap();
+        assert!(custom.change_path.is_none() && custom.change_addresses.is_empty());
+    }
+
+    #[test]
+    fn test_mnemonic_languages() {
+        // All-zero entropy in Japanese, written with ideographic spaces
+        let japanese = Mnemonic::from_entropy_in(Language::Japanese, &[0; 16]).unwrap();
+        let phrase = mnemonic_phrase(&japanese);
+        assert!(phrase.contains('\u{3000}'));
+
+        // Detected on import; precomposed kana (ぞ U+305E) normalize to the word list's NFKD form
+        let composed = phrase.replace("\u{305d}\u{3099}", "\u{305e}");
+        assert_ne!(composed, phrase);
+        let parsed = parse_mnemonic(&composed, None).unwrap();
+        assert_eq!(parsed.language(), Language::Japanese);
+        assert_eq!(parsed.to_seed(""), japanese.to_seed(""));
+
+        let spanish = Mnemonic::from_entropy_in(Language::Spanish, &[0; 16]).unwrap().to_string();
+        assert_eq!(parse_mnemonic(&spanish, None).unwrap().language(), Language::Spanish);
+        assert!(parse_mnemonic(&spanish, Some(Language::English)).is_err());
+    }
+
+    #[test]
+    fn test_invalid_mnemonic_import() {
+        assert!(import_wallet("abandon abandon", None, None, None, "", Network::Bitcoin, 0..1).is_err());
     }
 }
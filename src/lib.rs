@@ -0,0 +1,25 @@
+//! Bitcoin Address Toolkit library
+//!
+//! Pure functions for validating addresses, deriving HD wallets and converting
+//! public keys between address formats. Every entry point returns a typed value
+//! instead of printing, so the same logic backs the `btc-tools` binary and can
+//! be called from other Rust code.
+//!
+//! ⚠️ FOR EDUCATIONAL PURPOSES ONLY - Never use generated keys for real funds!
+
+pub mod converter;
+pub mod descriptor;
+pub mod entropy;
+pub mod hd_wallet;
+pub mod mnemonic_recovery;
+pub mod path_finder;
+pub mod recovery;
+pub mod script;
+pub mod slip132;
+pub mod validator;
+
+pub mod utils;
+
+pub use converter::ConversionReport;
+pub use hd_wallet::{DerivedAddress, WalletReport, WatchOnlyReport};
+pub use validator::AddressInfo;
@@ -1,12 +1,16 @@
 use clap::{Parser, Subcommand};
 use colored::*;
-use anyhow::Result;
+use std::process::ExitCode;
+use anyhow::{Result, bail};
+use btc_address_tools::hd_wallet;
 
-mod validator;
-mod hd_wallet;
-mod converter;
-mod educational;
-mod utils;
+mod cli;
+
+use cli::{convert, descriptor, educational, recover, validate, wallet, AddressKindArg, LanguageArg, NetworkArg, ScriptTypeArg};
+use cli::descriptor::DescriptorArgs;
+use cli::recover::{FindPathArgs, RecoverMnemonicArgs};
+use cli::wallet::{DeriveXpubArgs, EntropyArgs, GenerateArgs, ImportArgs, KeyArgs, MnemonicArgs};
+use cli::output::OutputFormat;
 
 #[derive(Parser)]
 #[command(
@@ -16,6 +20,10 @@ mod utils;
     long_about = "A comprehensive Bitcoin address toolkit for validation, HD wallet generation, and learning.\n⚠️  FOR EDUCATIONAL PURPOSES ONLY - Never use generated keys for real funds!"
 )]
 struct Cli {
+    /// Output format (json, yaml, tsv and csv are machine-readable)
+    #[arg(long, global = true, value_enum, default_value = "text")]
+    output: OutputFormat,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -25,50 +33,79 @@ enum Commands {
     /// Validate a Bitcoin address (supports Legacy, P2SH, SegWit, Taproot)
     Validate {
         /// Bitcoin address to validate
-        address: String,
+        #[arg(required_unless_present = "file")]
+        address: Option<String>,
+
+        /// Validate one address per line from a file ("-" reads stdin)
+        #[arg(short, long, value_name = "PATH", conflicts_with = "address")]
+        file: Option<String>,
+
+        /// Require the address to belong to this network
+        #[arg(short, long, value_enum)]
+        network: Option<NetworkArg>,
     },
     
     /// Generate a new HD wallet with BIP39 mnemonic
-    Generate {
-        /// Number of words in mnemonic (12 or 24)
-        #[arg(short, long, default_value = "12")]
-        words: usize,
-        
-        /// Number of addresses to derive
-        #[arg(short, long, default_value = "5")]
-        count: usize,
-        
-        /// Show private keys (⚠️ USE WITH CAUTION)
-        #[arg(long)]
-        show_private_keys: bool,
-    },
+    Generate(GenerateArgs),
     
     /// Import and recover wallet from existing mnemonic
-    Import {
-        /// BIP39 mnemonic phrase (12 or 24 words)
-        #[arg(short, long)]
-        mnemonic: Option<String>,
-        
-        /// Custom derivation path (default: m/44'/0'/0'/0)
-        #[arg(short, long, default_value = "m/44'/0'/0'/0")]
-        path: String,
-        
-        /// Number of addresses to derive
-        #[arg(short, long, default_value = "5")]
-        count: usize,
-        
-        /// Show private keys (⚠️ USE WITH CAUTION)
-        #[arg(long)]
-        show_private_keys: bool,
-    },
+    Import(ImportArgs),
+
+    /// Derive watch-only receive/change addresses from an account xpub/ypub/zpub
+    DeriveXpub(DeriveXpubArgs),
+
+    /// Parse an output descriptor, verify its checksum and derive its addresses
+    Descriptor(DescriptorArgs),
+
+    /// Recover a mnemonic with misspelled, missing or swapped words
+    RecoverMnemonic(RecoverMnemonicArgs),
+
+    /// Find which derivation path of a mnemonic produced an address
+    FindPath(FindPathArgs),
     
     /// Convert between different Bitcoin address formats
     Convert {
-        /// Public key (hex) or any Bitcoin address
+        /// Public key (hex), private key (WIF or 64 hex characters) or any Bitcoin address
         input: String,
-        /// Generate testnet addresses instead of mainnet
+        /// Generate testnet addresses instead of mainnet (default for a WIF key: its own network)
         #[arg(long)]
         testnet: bool,
+        /// Show the Taproot key tweak and output key
+        #[arg(short, long)]
+        verbose: bool,
+        /// Print a private key input back as WIF for each network (⚠️ USE WITH CAUTION)
+        #[arg(long)]
+        show_private_keys: bool,
+    },
+    
+    /// Decode an address: version byte or HRP, payload, checksum, scriptPubKey and Electrum script hash
+    Inspect {
+        /// Bitcoin address to decode
+        address: String,
+    },
+    
+    /// Re-encode an address's hash for another network or address type
+    Reencode {
+        /// Bitcoin address whose hash is re-encoded
+        address: String,
+        /// Only this address type (default: every type that shares the hash)
+        #[arg(long, value_enum)]
+        to: Option<AddressKindArg>,
+        /// Only this network (default: mainnet, testnet and regtest)
+        #[arg(short, long, value_enum)]
+        network: Option<NetworkArg>,
+    },
+    
+    /// Turn a scriptPubKey, or a redeem/witness script, into its addresses
+    ScriptToAddress {
+        /// Script in hex or ASM (quote ASM so it stays one argument)
+        script: String,
+        /// Treat the script as a redeem or witness script and give its P2SH, P2SH-P2WSH and P2WSH addresses
+        #[arg(long)]
+        redeem_script: bool,
+        /// Network of the addresses
+        #[arg(short, long, value_enum, default_value = "mainnet")]
+        network: NetworkArg,
     },
     
     /// Generate example data for testing (e.g., public key)
@@ -81,33 +118,59 @@ enum Commands {
     Interactive,
 }
 
-fn main() -> Result<()> {
+fn main() -> Result<ExitCode> {
     let cli = Cli::parse();
     
-    // Display security warning banner
-    display_banner();
+    let output = cli.output;
+    
+    // Display security warning banner (kept out of machine-readable output)
+    if output.is_text() {
+        display_banner();
+    } else if matches!(cli.command, Commands::Learn | Commands::Interactive) {
+        bail!("Structured --output is not supported by interactive commands");
+    }
     
     match cli.command {
-        Commands::Validate { address } => {
-            validator::validate_address(&address)?;
+        Commands::Validate { address, file, network } => {
+            let network = network.map(Into::into);
+            return match (file, address) {
+                (Some(path), _) => validate::run_batch(&path, network, output),
+                (None, Some(address)) => validate::run(&address, network, output),
+                (None, None) => unreachable!("clap requires an address or --file"),
+            };
+        }
+        Commands::Generate(args) => {
+            wallet::run_generate(args, output)?;
+        }
+        Commands::Import(args) => {
+            wallet::run_import(args, output)?;
         }
-        Commands::Generate { words, count, show_private_keys } => {
-            hd_wallet::generate_wallet(words, count, show_private_keys)?;
+        Commands::DeriveXpub(args) => {
+            wallet::run_derive_xpub(args, output)?;
         }
-        Commands::Import { mnemonic, path, count, show_private_keys } => {
-            hd_wallet::import_wallet(mnemonic, &path, count, show_private_keys)?;
+        Commands::Descriptor(args) => {
+            descriptor::run(args, output)?;
         }
-        Commands::Convert { input, testnet } => {
-            converter::convert_formats(&input, testnet)?;
+        Commands::RecoverMnemonic(args) => {
+            recover::run(args, output)?;
+        }
+        Commands::FindPath(args) => {
+            recover::run_find_path(args, output)?;
+        }
+        Commands::Convert { input, testnet, verbose, show_private_keys } => {
+            convert::run(&input, testnet, verbose, show_private_keys, output)?;
+        }
+        Commands::Inspect { address } => {
+            convert::run_inspect(&address, output)?;
+        }
+        Commands::Reencode { address, to, network } => {
+            convert::run_reencode(&address, to.map(Into::into), network.map(Into::into), output)?;
+        }
+        Commands::ScriptToAddress { script, redeem_script, network } => {
+            convert::run_script_to_address(&script, redeem_script, network.into(), output)?;
         }
         Commands::Example => {
-            let pubkey = converter::generate_example_pubkey()?;
-            println!("\n{}", "🔑 Example Data Generated".bright_cyan().bold());
-            println!("{}", "━".repeat(30).cyan());
-            println!("{} {}", "Sample Public Key (hex):".bold(), pubkey.to_string().bright_white());
-            println!("{}", "━".repeat(30).cyan());
-            println!("You can use this public key with the 'convert' command.");
-            println!("Example: {} convert {}", "btc-tools".green(), pubkey.to_string().bright_white());
+            convert::run_example(true, output)?;
         }
         Commands::Learn => {
             educational::run_tutorial()?;
@@ -117,7 +180,7 @@ fn main() -> Result<()> {
         }
     }
     
-    Ok(())
+    Ok(ExitCode::SUCCESS)
 }
 
 fn display_banner() {
@@ -157,50 +220,66 @@ fn run_interactive_mode() -> Result<()> {
                 let address: String = dialoguer::Input::new()
                     .with_prompt("Enter Bitcoin address to validate")
                     .interact_text()?;
-                validator::validate_address(&address)?;
+                validate::run(&address, None, OutputFormat::Text)?;
             }
             1 => {
-                let words_options = vec!["12 words", "24 words"];
+                let words_options: Vec<String> = hd_wallet::WORD_COUNTS.iter()
+                    .map(|words| format!("{} words ({} bits)", words, words / 3 * 32))
+                    .collect();
                 let words_selection = Select::with_theme(&ColorfulTheme::default())
                     .with_prompt("Select mnemonic length")
                     .items(&words_options)
                     .default(0)
                     .interact()?;
-                let words = if words_selection == 0 { 12 } else { 24 };
+                let words = hd_wallet::WORD_COUNTS[words_selection];
                 
-                let count: usize = dialoguer::Input::new()
+                let count: u32 = dialoguer::Input::new()
                     .with_prompt("Number of addresses to generate")
                     .default(5)
                     .interact_text()?;
                 
+                let passphrase = ask_passphrase()?;
+
                 let show_private = dialoguer::Confirm::new()
                     .with_prompt("⚠️  Show private keys? (DANGEROUS - educational only)")
                     .default(false)
                     .interact()?;
                 
-                hd_wallet::generate_wallet(words, count, show_private)?;
+                let args = GenerateArgs {
+                    words,
+                    language: LanguageArg::English,
+                    script_type: ScriptTypeArg::P2wpkh,
+                    entropy: EntropyArgs::default(),
+                    keys: KeyArgs { count, from: 0, passphrase, network: NetworkArg::Mainnet, show_private_keys: show_private },
+                };
+                wallet::run_generate(args, OutputFormat::Text)?;
             }
             2 => {
-                let mnemonic: String = dialoguer::Input::new()
-                    .with_prompt("Enter your BIP39 mnemonic (12 or 24 words)")
-                    .interact_text()?;
-                
                 let path: String = dialoguer::Input::new()
                     .with_prompt("Derivation path")
-                    .default("m/44'/0'/0'/0".to_string())
+                    .default(hd_wallet::DEFAULT_DERIVATION_PATH.to_string())
                     .interact_text()?;
                 
-                let count: usize = dialoguer::Input::new()
+                let count: u32 = dialoguer::Input::new()
                     .with_prompt("Number of addresses to derive")
                     .default(5)
                     .interact_text()?;
                 
+                let passphrase = ask_passphrase()?;
+
                 let show_private = dialoguer::Confirm::new()
                     .with_prompt("⚠️  Show private keys?")
                     .default(false)
                     .interact()?;
                 
-                hd_wallet::import_wallet(Some(mnemonic), &path, count, show_private)?;
+                let args = ImportArgs {
+                    mnemonic: MnemonicArgs::default(),
+                    language: None,
+                    path: Some(path),
+                    script_type: None,
+                    keys: KeyArgs { count, from: 0, passphrase, network: NetworkArg::Mainnet, show_private_keys: show_private },
+                };
+                wallet::run_import(args, OutputFormat::Text)?;
             }
             3 => {
                 let input: String = dialoguer::Input::new()
@@ -212,18 +291,13 @@ fn run_interactive_mode() -> Result<()> {
                     .default(false)
                     .interact()?;
 
-                converter::convert_formats(&input, testnet)?;
+                convert::run(&input, testnet, false, false, OutputFormat::Text)?;
             }
             4 => {
                 educational::run_tutorial()?;
             }
             5 => {
-                let pubkey = converter::generate_example_pubkey()?;
-                println!("\n{}", "🔑 Example Data Generated".bright_cyan().bold());
-                println!("{}", "━".repeat(30).cyan());
-                println!("{} {}", "Sample Public Key (hex):".bold(), pubkey.to_string().bright_white());
-                println!("{}", "━".repeat(30).cyan());
-                println!("You can use this public key with the 'convert' command.");
+                convert::run_example(false, OutputFormat::Text)?;
             }
             6 => {
                 println!("\n{}\n", "👋 Goodbye! Stay safe with your Bitcoin!".bright_green());
@@ -235,3 +309,14 @@ fn run_interactive_mode() -> Result<()> {
     
     Ok(())
 }
+
+/// Offer to add a BIP39 passphrase in interactive mode
+///
+/// Returns the `--passphrase` argument to use: the bare flag prompts later.
+fn ask_passphrase() -> Result<Option<Option<String>>> {
+    let use_passphrase = dialoguer::Confirm::new()
+        .with_prompt("Use a BIP39 passphrase (25th word)?")
+        .default(false)
+        .interact()?;
+    Ok(use_passphrase.then_some(None))
+}
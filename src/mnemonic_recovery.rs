@@ -0,0 +1,517 @@
+//! Recovery of damaged BIP39 mnemonics
+//!
+//! Every mnemonic carries a checksum of 4 to 8 bits, so only 1 in 16 to 1 in
+//! 256 arbitrary word sequences is valid. That is too weak to pick a single
+//! answer, but strong enough to cut a search over misspelled, missing or
+//! swapped words down to a short list. A known address from the wallet then
+//! singles out the right candidate.
+
+use anyhow::{Result, bail};
+use bip39::{Language, Mnemonic};
+use bitcoin::bip32::DerivationPath;
+use bitcoin::hashes::{sha256, Hash};
+use bitcoin::{Network, ScriptBuf};
+use std::borrow::Cow;
+use std::fmt;
+
+use crate::hd_wallet::{self, ScriptType, GAP_LIMIT};
+use crate::validator;
+
+/// Most candidates returned; the total is still counted
+pub const MAX_CANDIDATES: usize = 20;
+
+/// Most replacements suggested for one unknown word
+pub const MAX_WORD_SUGGESTIONS: usize = 8;
+
+/// Largest number of phrases a search may try
+///
+/// One word missing at an unknown position needs 24,576 (12 words) to
+/// 49,152 (24 words). Two would need C(n, 2) × 2048², from 277 million
+/// (12 words) to 1.16 billion (24 words), and with an address every
+/// phrase passing the checksum costs a seed derivation, so `missing` is
+/// limited to 1 and two known-position words (`? ?`, 4.2 million) is
+/// the two-word case supported.
+pub const MAX_SEARCH: u64 = 50_000_000;
+
+/// Tokens that mark a missing word at a known position
+const PLACEHOLDERS: [&str; 3] = ["?", "_", "*"];
+
+/// One change that turns the input into a candidate
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fix {
+    /// An unknown or mistyped word was replaced
+    Replaced { position: usize, from: String, to: &'static str },
+    /// A `?` placeholder was filled in
+    Filled { position: usize, word: &'static str },
+    /// A word missing at an unknown position was inserted
+    Inserted { position: usize, word: &'static str },
+    /// Two words were in each other's place
+    Swapped { first: usize, second: usize },
+}
+
+impl fmt::Display for Fix {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Fix::Replaced { position, from, to } => write!(f, "word {}: '{}' → '{}'", position, from, to),
+            Fix::Filled { position, word } => write!(f, "word {}: ? → '{}'", position, word),
+            Fix::Inserted { position, word } => write!(f, "inserted '{}' as word {}", word, position),
+            Fix::Swapped { first, second } => write!(f, "swapped words {} and {}", first, second),
+        }
+    }
+}
+
+/// A phrase with a valid checksum that the input may have been meant as
+#[derive(Debug, Clone)]
+pub struct MnemonicCandidate {
+    pub mnemonic: Mnemonic,
+    /// Changes from the input, by position
+    pub fixes: Vec<Fix>,
+    /// Path of the target address, when one was given and found
+    pub address_path: Option<DerivationPath>,
+}
+
+/// A word that is not in the word list
+#[derive(Debug, Clone)]
+pub struct UnknownWord {
+    /// 1-based position in the input
+    pub position: usize,
+    pub word: String,
+    /// Closest word list entries, best first
+    pub suggestions: Vec<&'static str>,
+}
+
+/// Result of a mnemonic recovery search
+#[derive(Debug, Clone)]
+pub struct MnemonicRecovery {
+    /// Best candidates first, at most [`MAX_CANDIDATES`]; with a target
+    /// address, only the one that derives it
+    pub candidates: Vec<MnemonicCandidate>,
+    /// Phrases tried
+    pub searched: u64,
+    /// Phrases with a valid checksum
+    pub valid_checksums: u64,
+    /// Whether candidates were checked against a target address
+    pub address_checked: bool,
+    pub unknown_words: Vec<UnknownWord>,
+}
+
+/// Wallet address a candidate must derive, within the gap limit of the
+/// standard account for its type
+struct AddressTarget<'a> {
+    script_pubkey: ScriptBuf,
+    script_type: ScriptType,
+    network: Network,
+    passphrase: &'a str,
+}
+
+/// One word of the phrase being searched
+enum Slot {
+    Known(u16),
+    /// Unknown word, replaced by one of the suggestions
+    Replace { from: String, choices: Vec<u16> },
+    /// `?` placeholder or word inserted at a guessed position
+    Any { inserted: bool },
+}
+
+/// Search for valid mnemonics close to `input`
+///
+/// `input` may contain misspelled words and `?` for missing words at known
+/// positions; `missing` more words (0 or 1) may be absent at unknown positions.
+/// A phrase whose words are all valid but whose checksum fails is searched
+/// for two swapped words and for single words mistyped into other valid
+/// words. With `target_address`, candidates are checked against the
+/// wallet's first receive and change addresses.
+pub fn recover_mnemonic(
+    input: &str,
+    missing: usize,
+    language: Language,
+    target_address: Option<&str>,
+    passphrase: &str,
+) -> Result<MnemonicRecovery> {
+    let mut normalized = Cow::Owned(input.trim().to_lowercase());
+    Mnemonic::normalize_utf8_cow(&mut normalized);
+    let words: Vec<&str> = normalized.split_whitespace().collect();
+
+    let total = words.len() + missing;
+    if missing > 1 {
+        bail!("At most 1 word can be missing at an unknown position; mark the others with ? where you know their position");
+    }
+    if !hd_wallet::WORD_COUNTS.contains(&total) {
+        bail!("{} words plus {} missing is {}; BIP39 mnemonics have 12, 15, 18, 21 or 24 words",
+              words.len(), missing, total);
+    }
+    let target = target_address.map(|address| address_target(address, passphrase)).transpose()?;
+
+    let mut unknown_words = Vec::new();
+    let slots: Vec<Slot> = words.iter().enumerate()
+        .map(|(i, &word)| {
+            if PLACEHOLDERS.contains(&word) {
+                return Slot::Any { inserted: false };
+            }
+            if let Some(index) = language.find_word(word) {
+                return Slot::Known(index);
+            }
+            let suggestions = suggest_words(word, language);
+            unknown_words.push(UnknownWord { position: i + 1, word: word.to_string(), suggestions: suggestions.clone() });
+            match suggestions.is_empty() {
+                // Nothing close: the word may be anything
+                true => Slot::Any { inserted: false },
+                false => Slot::Replace {
+                    from: word.to_string(),
+                    choices: suggestions.iter().filter_map(|s| language.find_word(s)).collect(),
+                },
+            }
+        })
+        .collect();
+
+    let mut search = Search::new(language, target);
+    let fully_known = missing == 0 && slots.iter().all(|slot| matches!(slot, Slot::Known(_)));
+    if fully_known {
+        let indices: Vec<u16> = slots.iter().map(|slot| match slot { Slot::Known(i) => *i, _ => unreachable!() }).collect();
+        search.try_phrase(&indices, Vec::new())?;
+        if search.candidates.is_empty() {
+            search_swaps_and_typos(&mut search, &indices)?;
+        }
+    } else {
+        let combinations = search_size(&slots, missing);
+        if combinations > MAX_SEARCH {
+            let count = match combinations {
+                u64::MAX => format!("more than {}", u64::MAX),
+                count => count.to_string(),
+            };
+            bail!("Searching {} phrases is too slow; mark missing words with ? where you know their position", count);
+        }
+        for positions in insert_positions(total, missing) {
+            search_template(&mut search, &slots, &positions)?;
+            if search.done() {
+                break;
+            }
+        }
+    }
+
+    Ok(MnemonicRecovery {
+        candidates: search.candidates,
+        searched: search.searched,
+        valid_checksums: search.valid_checksums,
+        address_checked: search.target.is_some(),
+        unknown_words,
+    })
+}
+
+/// Word list entries close to `word`: sharing its first four letters (which
+/// identify an English word) or within two typos, best first
+pub fn suggest_words(word: &str, language: Language) -> Vec<&'static str> {
+    let prefix: String = word.chars().take(4).collect();
+    let max_distance = if word.chars().count() <= 3 { 1 } else { 2 };
+
+    let mut scored: Vec<(usize, &'static str)> = language.word_list().iter()
+        .filter_map(|&candidate| {
+            let distance = edit_distance(word, candidate);
+            if prefix.chars().count() == 4 && candidate.starts_with(&prefix) {
+                Some((0, candidate))
+            } else if distance <= max_distance {
+                Some((distance, candidate))
+            } else {
+                None
+            }
+        })
+        .collect();
+    scored.sort();
+    scored.into_iter().map(|(_, candidate)| candidate).take(MAX_WORD_SUGGESTIONS).collect()
+}
+
+/// Levenshtein distance counting a swap of adjacent letters as one edit
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    rows[0] = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            rows[i][j] = (rows[i - 1][j] + 1).min(rows[i][j - 1] + 1).min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                rows[i][j] = rows[i][j].min(rows[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    rows[a.len()][b.len()]
+}
+
+/// Whether word indices form a mnemonic with a valid checksum
+fn checksum_valid(indices: &[u16]) -> bool {
+    let total_bits = indices.len() * 11;
+    let checksum_bits = total_bits / 33;
+    let entropy_bytes = (total_bits - checksum_bits) / 8;
+
+    let mut bytes = [0u8; 33];
+    for (i, &index) in indices.iter().enumerate() {
+        for j in 0..11 {
+            if (index >> (10 - j)) & 1 == 1 {
+                let bit = i * 11 + j;
+                bytes[bit / 8] |= 1 << (7 - bit % 8);
+            }
+        }
+    }
+    let hash = sha256::Hash::hash(&bytes[..entropy_bytes]);
+    bytes[entropy_bytes] >> (8 - checksum_bits) == hash[0] >> (8 - checksum_bits)
+}
+
+fn address_target<'a>(address: &str, passphrase: &'a str) -> Result<AddressTarget<'a>> {
+    let info = validator::validate_address(address, None)?;
+    let Some(script_type) = info.address_type.and_then(ScriptType::from_address_type) else {
+        bail!("A {} address is not derived from a single-key wallet", info.type_name());
+    };
+    Ok(AddressTarget { script_pubkey: info.script_pubkey, script_type, network: info.network, passphrase })
+}
+
+impl AddressTarget<'_> {
+    /// Path of the target address if `mnemonic` derives it within the gap
+    /// limit of its standard account
+    fn find(&self, mnemonic: &Mnemonic) -> Result<Option<DerivationPath>> {
+        let seed = mnemonic.to_seed(self.passphrase);
+        let receive = self.script_type.default_path(self.network);
+        let account = hd_wallet::account_path(&receive);
+        let change = hd_wallet::change_path(&receive, &account);
+
+        for path in std::iter::once(receive).chain(change) {
+            let addresses = hd_wallet::derive_addresses(&seed, &path, self.script_type, self.network, 0..GAP_LIMIT)?;
+            if let Some(found) = addresses.iter().find(|derived| derived.address.script_pubkey() == self.script_pubkey) {
+                return Ok(Some(found.path.clone()));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Candidate collection shared by every search strategy
+struct Search<'a> {
+    language: Language,
+    target: Option<AddressTarget<'a>>,
+    candidates: Vec<MnemonicCandidate>,
+    searched: u64,
+    valid_checksums: u64,
+}
+
+impl<'a> Search<'a> {
+    fn new(language: Language, target: Option<AddressTarget<'a>>) -> Self {
+        Search { language, target, candidates: Vec::new(), searched: 0, valid_checksums: 0 }
+    }
+
+    /// A target address has one answer; stop once it is found
+    fn done(&self) -> bool {
+        self.target.is_some() && !self.candidates.is_empty()
+    }
+
+    fn try_phrase(&mut self, indices: &[u16], fixes: Vec<Fix>) -> Result<()> {
+        self.searched += 1;
+        if !checksum_valid(indices) {
+            return Ok(());
+        }
+        self.valid_checksums += 1;
+        if self.candidates.len() >= MAX_CANDIDATES && self.target.is_none() {
+            return Ok(());
+        }
+
+        let word_list = self.language.word_list();
+        let phrase: Vec<&str> = indices.iter().map(|&i| word_list[i as usize]).collect();
+        let mnemonic = Mnemonic::parse_in_normalized(self.language, &phrase.join(" "))?;
+        // Inserting a word into a run of the same word gives one phrase many ways
+        if self.candidates.iter().any(|candidate| candidate.mnemonic == mnemonic) {
+            return Ok(());
+        }
+        let address_path = match &self.target {
+            Some(target) => match target.find(&mnemonic)? {
+                Some(path) => Some(path),
+                None => return Ok(()),
+            },
+            None => None,
+        };
+        self.candidates.push(MnemonicCandidate { mnemonic, fixes, address_path });
+        Ok(())
+    }
+}
+
+/// Phrases tried by [`search_template`] over every insertion, saturating at
+/// `u64::MAX` (always over [`MAX_SEARCH`])
+fn search_size(slots: &[Slot], missing: usize) -> u64 {
+    let per_slot = slots.iter()
+        .map(|slot| match slot {
+            Slot::Known(_) => 1,
+            Slot::Replace { choices, .. } => choices.len() as u64,
+            Slot::Any { .. } => 2048,
+        })
+        .chain(std::iter::repeat_n(2048, missing))
+        .chain(std::iter::once(insert_positions(slots.len() + missing, missing).len() as u64));
+    per_slot.fold(1u64, u64::saturating_mul)
+}
+
+/// Every choice of `missing` (0 or 1) positions, 0-based, in a phrase of
+/// `total` words
+fn insert_positions(total: usize, missing: usize) -> Vec<Vec<usize>> {
+    match missing {
+        0 => vec![Vec::new()],
+        _ => (0..total).map(|i| vec![i]).collect(),
+    }
+}
+
+/// Try every filling of the open slots, with words inserted at `positions`
+fn search_template(search: &mut Search, slots: &[Slot], positions: &[usize]) -> Result<()> {
+    let mut given = slots.iter();
+    let phrase: Vec<Slot> = (0..slots.len() + positions.len())
+        .map(|i| match positions.contains(&i) {
+            true => Slot::Any { inserted: true },
+            false => match given.next().expect("one slot per remaining position") {
+                Slot::Known(index) => Slot::Known(*index),
+                Slot::Replace { from, choices } => Slot::Replace { from: from.clone(), choices: choices.clone() },
+                Slot::Any { inserted } => Slot::Any { inserted: *inserted },
+            },
+        })
+        .collect();
+
+    let all: Vec<u16> = (0..2048).collect();
+    let choices: Vec<&[u16]> = phrase.iter()
+        .map(|slot| match slot {
+            Slot::Known(index) => std::slice::from_ref(index),
+            Slot::Replace { choices, .. } => choices.as_slice(),
+            Slot::Any { .. } => all.as_slice(),
+        })
+        .collect();
+
+    // Odometer over the choices, last position turning fastest
+    let mut counters = vec![0usize; phrase.len()];
+    let word_list = search.language.word_list();
+    loop {
+        let indices: Vec<u16> = counters.iter().zip(&choices).map(|(&c, options)| options[c]).collect();
+        let fixes = || phrase.iter().zip(&indices).enumerate()
+            .filter_map(|(i, (slot, &index))| {
+                let word = word_list[index as usize];
+                match slot {
+                    Slot::Known(_) => None,
+                    Slot::Replace { from, .. } => Some(Fix::Replaced { position: i + 1, from: from.clone(), to: word }),
+                    Slot::Any { inserted: false } => Some(Fix::Filled { position: i + 1, word }),
+                    Slot::Any { inserted: true } => Some(Fix::Inserted { position: i + 1, word }),
+                }
+            })
+            .collect();
+        if checksum_valid(&indices) {
+            search.try_phrase(&indices, fixes())?;
+        } else {
+            search.searched += 1;
+        }
+        if search.done() {
+            return Ok(());
+        }
+
+        let Some(position) = (0..counters.len()).rev().find(|&i| counters[i] + 1 < choices[i].len()) else {
+            return Ok(());
+        };
+        counters[position] += 1;
+        for counter in &mut counters[position + 1..] {
+            *counter = 0;
+        }
+    }
+}
+
+/// Try every swap of two words, then every word mistyped as another valid
+/// word close to it
+fn search_swaps_and_typos(search: &mut Search, indices: &[u16]) -> Result<()> {
+    for first in 0..indices.len() {
+        for second in first + 1..indices.len() {
+            if indices[first] == indices[second] {
+                continue;
+            }
+            let mut swapped = indices.to_vec();
+            swapped.swap(first, second);
+            search.try_phrase(&swapped, vec![Fix::Swapped { first: first + 1, second: second + 1 }])?;
+            if search.done() {
+                return Ok(());
+            }
+        }
+    }
+
+    let word_list = search.language.word_list();
+    for (position, &index) in indices.iter().enumerate() {
+        let from = word_list[index as usize];
+        for to in suggest_words(from, search.language).into_iter().filter(|&to| to != from) {
+            let mut replaced = indices.to_vec();
+            replaced[position] = search.language.find_word(to).expect("suggestions come from the word list");
+            let fix = Fix::Replaced { position: position + 1, from: from.to_string(), to };
+            search.try_phrase(&replaced, vec![fix])?;
+            if search.done() {
+                return Ok(());
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    // First BIP84 receive address of TEST_MNEMONIC
+    const TEST_ADDRESS: &str = "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu";
+
+    #[test]
+    fn test_suggest_words() {
+        assert_eq!(suggest_words("abandn", Language::English)[0], "abandon");
+        assert_eq!(suggest_words("abotu", Language::English)[0], "about");
+        assert!(suggest_words("qqqqqqqq", Language::English).is_empty());
+        assert!(checksum_valid(&[0; 11].iter().copied().chain([3]).collect::<Vec<u16>>()));
+    }
+
+    #[test]
+    fn test_misspelled_and_placeholder() {
+        let input = TEST_MNEMONIC.replacen("abandon", "abandn", 1).replace("about", "?");
+        let recovery = recover_mnemonic(&input, 0, Language::English, None, "").unwrap();
+        assert_eq!(recovery.unknown_words[0].position, 1);
+        assert!(recovery.candidates.iter().any(|c| c.mnemonic.to_string() == TEST_MNEMONIC));
+
+        // The address check singles out the right last word
+        let recovery = recover_mnemonic(&input, 0, Language::English, Some(TEST_ADDRESS), "").unwrap();
+        assert_eq!(recovery.candidates.len(), 1);
+        assert_eq!(recovery.candidates[0].mnemonic.to_string(), TEST_MNEMONIC);
+        assert_eq!(recovery.candidates[0].address_path.as_ref().unwrap().to_string(), "m/84'/0'/0'/0/0");
+    }
+
+    #[test]
+    fn test_missing_word_at_unknown_position() {
+        let input = TEST_MNEMONIC.replacen("abandon ", "", 1);
+        let recovery = recover_mnemonic(&input, 1, Language::English, Some(TEST_ADDRESS), "").unwrap();
+        assert_eq!(recovery.candidates[0].mnemonic.to_string(), TEST_MNEMONIC);
+        assert!(matches!(recovery.candidates[0].fixes[0], Fix::Inserted { word: "abandon", .. }));
+
+        let input = TEST_MNEMONIC.replacen("abandon ", "", 2);
+        assert!(recover_mnemonic(&input, 2, Language::English, Some(TEST_ADDRESS), "").is_err());
+
+        // Two missing words are found when their positions are marked
+        let input = TEST_MNEMONIC.replacen("abandon abandon", "? ?", 1);
+        let recovery = recover_mnemonic(&input, 0, Language::English, Some(TEST_ADDRESS), "").unwrap();
+        assert_eq!(recovery.candidates[0].mnemonic.to_string(), TEST_MNEMONIC);
+        assert_eq!(recovery.candidates[0].fixes.len(), 2);
+    }
+
+    #[test]
+    fn test_search_too_large() {
+        // 2048^6 overflows u64; the search must still be refused
+        let input = format!("? ? ? ? ? ? {}", TEST_MNEMONIC.splitn(7, ' ').last().unwrap());
+        let error = recover_mnemonic(&input, 0, Language::English, None, "").unwrap_err();
+        assert!(error.to_string().contains("too slow"));
+        let error = recover_mnemonic(&"? ".repeat(24), 0, Language::English, None, "").unwrap_err();
+        assert!(error.to_string().contains("too slow"));
+    }
+
+    #[test]
+    fn test_swapped_words() {
+        let mnemonic = "legal winner thank year wave sausage worth useful legal winner thank yellow";
+        let swapped = "legal winner thank year wave worth sausage useful legal winner thank yellow";
+        let recovery = recover_mnemonic(swapped, 0, Language::English, None, "").unwrap();
+        let candidate = recovery.candidates.iter().find(|c| c.mnemonic.to_string() == mnemonic).unwrap();
+        assert_eq!(candidate.fixes, vec![Fix::Swapped { first: 6, second: 7 }]);
+    }
+}
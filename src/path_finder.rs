@@ -0,0 +1,344 @@
+//! Discovery of the derivation path behind an address
+//!
+//! Wallets agree on how a mnemonic becomes a seed, but not on where below
+//! the seed they put their addresses. Given the mnemonic and one address,
+//! [`find_path`] walks the layouts in common use, most widespread first,
+//! until one of them derives the address.
+//!
+//! Electrum's own seeds look like BIP39 mnemonics but are not: a version
+//! number hidden in their hash marks them, and the seed is stretched with
+//! a different salt. Such phrases are searched with Electrum's layouts.
+
+use anyhow::{Result, bail};
+use bip39::{Language, Mnemonic};
+use bitcoin::bip32::{ChildNumber, DerivationPath, Xpriv, Xpub};
+use bitcoin::hashes::{hmac, sha512, Hash, HashEngine};
+use bitcoin::secp256k1::Secp256k1;
+use bitcoin::{Network, PublicKey, ScriptBuf};
+use std::borrow::Cow;
+use std::fmt;
+
+use crate::hd_wallet::{self, ScriptType};
+use crate::validator;
+
+/// How a layout turns the phrase into a seed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeedKind {
+    /// BIP39 mnemonic and passphrase
+    Bip39,
+    /// Electrum seed of the given type
+    Electrum(ElectrumSeedType),
+}
+
+/// Electrum seed types with a single-key wallet
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ElectrumSeedType {
+    /// Version `01`: P2PKH addresses at `m/chain/index`
+    Standard,
+    /// Version `100`: P2WPKH addresses at `m/0'/chain/index`
+    Segwit,
+}
+
+impl fmt::Display for ElectrumSeedType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            ElectrumSeedType::Standard => "standard",
+            ElectrumSeedType::Segwit => "segwit",
+        })
+    }
+}
+
+/// Where below the seed a family of wallets puts its addresses
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Layout {
+    /// `m/purpose'/coin'/account'/chain/index`, purpose from the script type
+    Bip44,
+    /// `m/chain/index`
+    Root,
+    /// `m/0'/chain/index`
+    FirstAccount,
+    /// `m/0'/chain'/index'`, every step hardened
+    CoreLegacy,
+}
+
+/// A derivation path layout in common use
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PathScheme {
+    pub name: &'static str,
+    /// Wallet software known to use it
+    pub used_by: &'static str,
+    pub seed: SeedKind,
+    layout: Layout,
+    script_types: &'static [ScriptType],
+}
+
+/// Layouts searched, in order
+pub const SCHEMES: &[PathScheme] = &[
+    PathScheme {
+        name: "BIP44/49/84/86",
+        used_by: "Bitcoin Core descriptor wallets, Ledger Live, Trezor Suite, Sparrow, BlueWallet, Electrum with BIP39 seeds",
+        seed: SeedKind::Bip39,
+        layout: Layout::Bip44,
+        script_types: &ScriptType::ALL,
+    },
+    PathScheme {
+        name: "Electrum standard",
+        used_by: "Electrum standard wallets",
+        seed: SeedKind::Electrum(ElectrumSeedType::Standard),
+        layout: Layout::Root,
+        script_types: &[ScriptType::P2pkh],
+    },
+    PathScheme {
+        name: "Electrum SegWit",
+        used_by: "Electrum SegWit wallets",
+        seed: SeedKind::Electrum(ElectrumSeedType::Segwit),
+        layout: Layout::FirstAccount,
+        script_types: &[ScriptType::P2wpkh],
+    },
+    PathScheme {
+        name: "Bitcoin Core legacy",
+        used_by: "Bitcoin Core HD wallets before descriptors (0.13-0.20)",
+        seed: SeedKind::Bip39,
+        layout: Layout::CoreLegacy,
+        script_types: &[ScriptType::P2pkh, ScriptType::P2shP2wpkh, ScriptType::P2wpkh],
+    },
+    PathScheme {
+        name: "BIP32 account 0",
+        used_by: "MultiBit HD, early Breadwallet",
+        seed: SeedKind::Bip39,
+        layout: Layout::FirstAccount,
+        script_types: &[ScriptType::P2pkh],
+    },
+];
+
+/// Where the target address was found
+#[derive(Debug, Clone)]
+pub struct PathMatch {
+    pub scheme: &'static PathScheme,
+    pub path: DerivationPath,
+    pub script_type: ScriptType,
+    /// Account number, for BIP44-style paths
+    pub account: Option<u32>,
+    /// 0 for receive, 1 for change
+    pub chain: u32,
+    pub index: u32,
+}
+
+/// Result of a derivation path search
+#[derive(Debug, Clone)]
+pub struct PathSearch {
+    pub found: Option<PathMatch>,
+    /// Addresses derived and compared
+    pub searched: u64,
+    /// Names of the layouts that apply to the phrase and address type
+    pub schemes: Vec<&'static str>,
+    /// Electrum seed type, when the phrase is an Electrum seed
+    pub electrum_seed: Option<ElectrumSeedType>,
+    pub script_type: ScriptType,
+    pub network: Network,
+}
+
+/// Search the common derivation paths of `mnemonic_str` for `target_address`
+///
+/// BIP44-style paths are searched for accounts `0..accounts`; every layout
+/// is searched for indexes `0..count` of the receive and change chains.
+pub fn find_path(
+    mnemonic_str: &str,
+    language: Option<Language>,
+    passphrase: &str,
+    target_address: &str,
+    accounts: u32,
+    count: u32,
+) -> Result<PathSearch> {
+    let info = validator::validate_address(target_address, None)?;
+    let Some(script_type) = info.address_type.and_then(ScriptType::from_address_type) else {
+        bail!("A {} address is not derived from a single-key wallet", info.type_name());
+    };
+    let network = info.network;
+
+    let electrum_seed = electrum_seed_type(mnemonic_str);
+    let bip39 = match hd_wallet::parse_mnemonic(mnemonic_str, language) {
+        Ok(mnemonic) => Some(mnemonic),
+        Err(_) if electrum_seed.is_some() => None,
+        Err(e) => return Err(e),
+    };
+
+    let secp = Secp256k1::new();
+    let mut search = PathSearch {
+        found: None,
+        searched: 0,
+        schemes: Vec::new(),
+        electrum_seed,
+        script_type,
+        network,
+    };
+    for scheme in SCHEMES.iter().filter(|scheme| scheme.script_types.contains(&script_type)) {
+        let seed = match (scheme.seed, &bip39) {
+            (SeedKind::Bip39, Some(mnemonic)) => mnemonic.to_seed(passphrase),
+            (SeedKind::Electrum(kind), _) if electrum_seed == Some(kind) => electrum_seed_bytes(mnemonic_str, passphrase),
+            _ => continue,
+        };
+        search.schemes.push(scheme.name);
+        let master = Xpriv::new_master(network, &seed)?;
+
+        let scheme_accounts = if scheme.layout == Layout::Bip44 { 0..accounts } else { 0..1 };
+        for account in scheme_accounts {
+            for chain in 0..2 {
+                let chain_path = chain_path(scheme.layout, script_type, network, account, chain)?;
+                let hardened = scheme.layout == Layout::CoreLegacy;
+                let chain_key = master.derive_priv(&secp, &chain_path)?;
+                if let Some(index) = search_chain(&chain_key, hardened, &info.script_pubkey, script_type, network, count, &mut search.searched)? {
+                    let child = child_number(index, hardened)?;
+                    search.found = Some(PathMatch {
+                        scheme,
+                        path: chain_path.child(child),
+                        script_type,
+                        account: (scheme.layout == Layout::Bip44).then_some(account),
+                        chain,
+                        index,
+                    });
+                    return Ok(search);
+                }
+            }
+        }
+    }
+    Ok(search)
+}
+
+/// Path of the receive (0) or change (1) chain of an account
+fn chain_path(layout: Layout, script_type: ScriptType, network: Network, account: u32, chain: u32) -> Result<DerivationPath> {
+    let steps = match layout {
+        Layout::Bip44 => vec![
+            ChildNumber::from_hardened_idx(script_type.purpose())?,
+            ChildNumber::from_hardened_idx(hd_wallet::coin_type(network))?,
+            ChildNumber::from_hardened_idx(account)?,
+            ChildNumber::from_normal_idx(chain)?,
+        ],
+        Layout::Root => vec![ChildNumber::from_normal_idx(chain)?],
+        Layout::FirstAccount => vec![ChildNumber::from_hardened_idx(0)?, ChildNumber::from_normal_idx(chain)?],
+        Layout::CoreLegacy => vec![ChildNumber::from_hardened_idx(0)?, ChildNumber::from_hardened_idx(chain)?],
+    };
+    Ok(DerivationPath::from(steps))
+}
+
+fn child_number(index: u32, hardened: bool) -> Result<ChildNumber> {
+    Ok(match hardened {
+        true => ChildNumber::from_hardened_idx(index)?,
+        false => ChildNumber::from_normal_idx(index)?,
+    })
+}
+
+/// Index below `chain_key` whose address has `target` as its scriptPubKey
+fn search_chain(
+    chain_key: &Xpriv,
+    hardened: bool,
+    target: &ScriptBuf,
+    script_type: ScriptType,
+    network: Network,
+    count: u32,
+    searched: &mut u64,
+) -> Result<Option<u32>> {
+    let secp = Secp256k1::new();
+    // Normal children come from the public key alone, which is cheaper
+    let chain_xpub = Xpub::from_priv(&secp, chain_key);
+    for index in 0..count {
+        *searched += 1;
+        let public_key = match hardened {
+            true => chain_key.derive_priv(&secp, &[child_number(index, true)?])?.to_priv().public_key(&secp),
+            false => PublicKey::new(chain_xpub.derive_pub(&secp, &[child_number(index, false)?])?.public_key),
+        };
+        if script_type.address(&secp, &public_key, network)?.script_pubkey() == *target {
+            return Ok(Some(index));
+        }
+    }
+    Ok(None)
+}
+
+/// Electrum's normalization: NFKD, lowercase, no accents, single spaces
+fn electrum_normalize(text: &str) -> String {
+    let mut normalized = Cow::Owned(text.to_lowercase());
+    Mnemonic::normalize_utf8_cow(&mut normalized);
+    let stripped: String = normalized.chars().filter(|c| !('\u{300}'..='\u{36f}').contains(c)).collect();
+    stripped.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn hmac_sha512(key: &[u8], messages: &[&[u8]]) -> [u8; 64] {
+    let mut engine = hmac::HmacEngine::<sha512::Hash>::new(key);
+    for message in messages {
+        engine.input(message);
+    }
+    hmac::Hmac::from_engine(engine).to_byte_array()
+}
+
+/// Type of an Electrum seed, read from the version prefix of
+/// HMAC-SHA512("Seed version", phrase); `None` for BIP39 mnemonics and
+/// Electrum's two-factor seeds
+pub fn electrum_seed_type(phrase: &str) -> Option<ElectrumSeedType> {
+    let version = hex::encode(&hmac_sha512(b"Seed version", &[electrum_normalize(phrase).as_bytes()])[..2]);
+    if version.starts_with("100") {
+        Some(ElectrumSeedType::Segwit)
+    } else if version.starts_with("01") {
+        Some(ElectrumSeedType::Standard)
+    } else {
+        None
+    }
+}
+
+/// Electrum seed: PBKDF2-HMAC-SHA512 over the phrase, salted with
+/// "electrum" and the passphrase, 2048 rounds
+fn electrum_seed_bytes(phrase: &str, passphrase: &str) -> [u8; 64] {
+    let password = electrum_normalize(phrase);
+    let salt = format!("electrum{}", electrum_normalize(passphrase));
+
+    // One 64-byte block: U1 = HMAC(salt || 1), Un = HMAC(Un-1), XORed together
+    let mut block = hmac_sha512(password.as_bytes(), &[salt.as_bytes(), &1u32.to_be_bytes()]);
+    let mut seed = block;
+    for _ in 1..2048 {
+        block = hmac_sha512(password.as_bytes(), &[&block]);
+        for (byte, next) in seed.iter_mut().zip(block) {
+            *byte ^= next;
+        }
+    }
+    seed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn test_bip44_paths() {
+        // BIP84 account 0, change index 0
+        let search = find_path(TEST_MNEMONIC, None, "", "bc1q8c6fshw2dlwun7ekn9qwf37cu2rn755upcp6el", 3, 20).unwrap();
+        let found = search.found.unwrap();
+        assert_eq!(found.path.to_string(), "m/84'/0'/0'/1/0");
+        assert_eq!((found.account, found.chain, found.index), (Some(0), 1, 0));
+
+        // BIP44 account 0, receive index 0
+        let search = find_path(TEST_MNEMONIC, None, "", "1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA", 3, 20).unwrap();
+        assert_eq!(search.found.unwrap().path.to_string(), "m/44'/0'/0'/0/0");
+
+        // Not within the searched range
+        let search = find_path(TEST_MNEMONIC, None, "", "1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA", 1, 0).unwrap();
+        assert!(search.found.is_none());
+    }
+
+    #[test]
+    fn test_electrum_seeds() {
+        // Vectors from Electrum's wallet tests
+        let standard = "cycle rocket west magnet parrot shuffle foot correct salt library feed song";
+        assert_eq!(electrum_seed_type(standard), Some(ElectrumSeedType::Standard));
+        let found = find_path(standard, None, "", "1KSezYMhAJMWqFbVFB2JshYg69UpmEXR4D", 1, 5).unwrap().found.unwrap();
+        assert_eq!(found.scheme.name, "Electrum standard");
+        assert_eq!(found.path.to_string(), "m/1/0");
+
+        let segwit = "bitter grass shiver impose acquire brush forget axis eager alone wine silver";
+        assert_eq!(electrum_seed_type(segwit), Some(ElectrumSeedType::Segwit));
+        let found = find_path(segwit, None, "", "bc1q3g5tmkmlvxryhh843v4dz026avatc0zzr6h3af", 1, 5).unwrap().found.unwrap();
+        assert_eq!(found.path.to_string(), "m/0'/0/0");
+
+        assert_eq!(electrum_seed_type(TEST_MNEMONIC), None);
+    }
+}
@@ -0,0 +1,340 @@
+//! Typo recovery for mistyped addresses
+//!
+//! Bech32 is a BCH code: the checksum residue of a corrupted string depends
+//! only on the error, not on the data. Precomputing the residue ("syndrome")
+//! of every single-character error lets us locate one or two wrong characters
+//! by table lookup instead of blind guessing.
+//!
+//! Base58Check has no such structure, but its 32-bit checksum makes false
+//! positives vanishingly rare, so every plausible edit can simply be tried.
+
+use std::collections::HashMap;
+
+use crate::validator;
+
+/// Maximum number of candidates returned by the Bech32 search
+pub const MAX_SUGGESTIONS: usize = 5;
+
+/// Lengths of real Base58Check addresses; other inputs are not searched
+pub const BASE58_ADDRESS_LENGTHS: std::ops::RangeInclusive<usize> = 25..=35;
+
+/// Longest Bech32 string (BIP173); longer inputs are not searched
+pub const MAX_BECH32_LENGTH: usize = 90;
+
+/// A corrected address that passes validation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Correction {
+    /// The corrected address
+    pub address: String,
+    /// 1-based positions of the characters that were changed
+    pub positions: Vec<usize>,
+}
+
+const BECH32_CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const BECH32_GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+const BECH32_CONST: u32 = 1;
+const BECH32M_CONST: u32 = 0x2bc830a3;
+
+/// Suggest corrections for a Bech32/Bech32m address with up to two wrong
+/// characters (or up to two characters outside the Bech32 alphabet)
+pub fn bech32_corrections(address: &str) -> Vec<Correction> {
+    if address.len() > MAX_BECH32_LENGTH {
+        return Vec::new();
+    }
+    let lower = address.to_lowercase();
+
+    // A case-only problem is fixed by normalizing
+    if lower != address && validator::validate_address(&lower, None).is_ok() {
+        let positions = address.chars().zip(lower.chars())
+            .enumerate()
+            .filter(|(_, (a, b))| a != b)
+            .map(|(i, _)| i + 1)
+            .collect();
+        return vec![Correction { address: lower, positions }];
+    }
+
+    let Some(sep) = lower.rfind('1') else { return Vec::new() };
+    let (hrp, data) = (&lower[..sep], &lower[sep + 1..]);
+    if hrp.is_empty() || data.len() < 6 || !hrp.is_ascii() {
+        return Vec::new();
+    }
+
+    // Map data characters to 5-bit values; unknown characters are erasures
+    let mut values = Vec::with_capacity(data.len());
+    let mut erasures = Vec::new();
+    for (i, c) in data.bytes().enumerate() {
+        match BECH32_CHARSET.iter().position(|&x| x == c) {
+            Some(v) => values.push(v as u8),
+            None => {
+                erasures.push(i);
+                values.push(0);
+            }
+        }
+    }
+
+    let offset = sep + 2; // 1-based position of the first data character
+    let mut search = Bech32Search { hrp, values, offset, found: Vec::new() };
+
+    match erasures.len() {
+        0 => search.locate_errors(),
+        1 | 2 => search.fill_erasures(&erasures),
+        _ => {}
+    }
+
+    search.found
+}
+
+struct Bech32Search<'a> {
+    hrp: &'a str,
+    values: Vec<u8>,
+    offset: usize,
+    found: Vec<Correction>,
+}
+
+impl Bech32Search<'_> {
+    fn residue(&self, values: &[u8]) -> u32 {
+        polymod(hrp_expand(self.hrp).into_iter().chain(values.iter().copied()), 1)
+    }
+
+    /// Syndrome lookup for one or two substituted characters
+    fn locate_errors(&mut self) {
+        let len = self.values.len();
+        let residue = self.residue(&self.values);
+
+        // Syndrome contributed by error value `e` at data index `i`
+        let singles: Vec<(u32, usize, u8)> = (0..len)
+            .flat_map(|i| (1..32u8).map(move |e| (i, e)))
+            .map(|(i, e)| (polymod(std::iter::once(e).chain(std::iter::repeat_n(0, len - 1 - i)), 0), i, e))
+            .collect();
+        let table: HashMap<u32, (usize, u8)> = singles.iter().map(|&(s, i, e)| (s, (i, e))).collect();
+
+        for constant in [BECH32_CONST, BECH32M_CONST] {
+            let syndrome = residue ^ constant;
+            if syndrome == 0 {
+                continue;
+            }
+            if let Some(&(i, e)) = table.get(&syndrome) {
+                self.try_candidate(&[(i, e)]);
+            }
+        }
+        if !self.found.is_empty() {
+            return;
+        }
+
+        for constant in [BECH32_CONST, BECH32M_CONST] {
+            let syndrome = residue ^ constant;
+            for &(first, i, e) in &singles {
+                if let Some(&(j, f)) = table.get(&(syndrome ^ first)) {
+                    if i < j {
+                        self.try_candidate(&[(i, e), (j, f)]);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Try every value at the positions of characters outside the alphabet
+    fn fill_erasures(&mut self, erasures: &[usize]) {
+        let combinations = 32usize.pow(erasures.len() as u32);
+        for n in 0..combinations {
+            let changes: Vec<(usize, u8)> = erasures.iter()
+                .enumerate()
+                .map(|(k, &i)| (i, ((n >> (5 * k)) & 31) as u8))
+                .collect();
+            self.try_candidate(&changes);
+        }
+    }
+
+    /// XOR (or, for erasures, set) values and keep the result if it validates
+    fn try_candidate(&mut self, changes: &[(usize, u8)]) {
+        if self.found.len() >= MAX_SUGGESTIONS {
+            return;
+        }
+        let mut values = self.values.clone();
+        for &(i, e) in changes {
+            values[i] ^= e;
+        }
+        let data: String = values.iter().map(|&v| BECH32_CHARSET[v as usize] as char).collect();
+        let address = format!("{}1{}", self.hrp, data);
+
+        if validator::validate_address(&address, None).is_ok()
+            && !self.found.iter().any(|c| c.address == address)
+        {
+            let positions = changes.iter().map(|&(i, _)| i + self.offset).collect();
+            self.found.push(Correction { address, positions });
+        }
+    }
+}
+
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Groups of characters that are easily mistaken for each other when read
+/// aloud or copied by hand; `0`, `O`, `I` and `l` are not in the alphabet
+const CONFUSABLES: &[&str] = &["0Oo", "Il1iL", "5Ss", "2Zz", "8B", "6G", "9gq", "Cc", "Kk", "Pp", "Uu", "Vv", "Ww", "Xx"];
+
+/// Most confusable characters corrected at once in one candidate
+const MAX_CONFUSABLE_EDITS: usize = 3;
+
+/// Suggest corrections for a Base58Check (legacy or P2SH) address
+///
+/// Tries every single-character substitution, every adjacent transposition
+/// and combinations of up to three confusable characters, returning all
+/// candidates with a valid checksum.
+pub fn base58_corrections(address: &str) -> Vec<Correction> {
+    let chars: Vec<char> = address.chars().collect();
+    // The confusable search grows with the cube of the length
+    if !BASE58_ADDRESS_LENGTHS.contains(&chars.len()) || !address.is_ascii() {
+        return Vec::new();
+    }
+
+    let mut found: Vec<Correction> = Vec::new();
+    let mut try_candidate = |candidate: Vec<char>| {
+        let address: String = candidate.iter().collect();
+        if found.iter().any(|c| c.address == address) || validator::validate_address(&address, None).is_err() {
+            return;
+        }
+        let positions = chars.iter().zip(&candidate)
+            .enumerate()
+            .filter(|(_, (a, b))| a != b)
+            .map(|(i, _)| i + 1)
+            .collect();
+        found.push(Correction { address, positions });
+    };
+
+    // Adjacent transpositions
+    for i in 0..chars.len() - 1 {
+        if chars[i] != chars[i + 1] {
+            let mut candidate = chars.clone();
+            candidate.swap(i, i + 1);
+            try_candidate(candidate);
+        }
+    }
+
+    // Single-character substitutions
+    for i in 0..chars.len() {
+        for &c in BASE58_ALPHABET {
+            if chars[i] != c as char {
+                let mut candidate = chars.clone();
+                candidate[i] = c as char;
+                try_candidate(candidate);
+            }
+        }
+    }
+
+    // Confusable characters swapped at up to MAX_CONFUSABLE_EDITS positions
+    let options: Vec<(usize, Vec<char>)> = chars.iter()
+        .enumerate()
+        .filter_map(|(i, &c)| {
+            let group = CONFUSABLES.iter().find(|group| group.contains(c))?;
+            let alternatives = group.chars()
+                .filter(|&x| x != c && BASE58_ALPHABET.contains(&(x as u8)))
+                .collect();
+            Some((i, alternatives))
+        })
+        .collect();
+    confusable_edits(&options, chars.clone(), MAX_CONFUSABLE_EDITS, &mut try_candidate);
+
+    found
+}
+
+/// Recursively apply up to `edits` confusable substitutions, later positions only
+fn confusable_edits(
+    options: &[(usize, Vec<char>)],
+    candidate: Vec<char>,
+    edits: usize,
+    try_candidate: &mut impl FnMut(Vec<char>),
+) {
+    if edits == 0 {
+        return;
+    }
+    for (k, (i, alternatives)) in options.iter().enumerate() {
+        for &alternative in alternatives {
+            let mut next = candidate.clone();
+            next[*i] = alternative;
+            try_candidate(next.clone());
+            confusable_edits(&options[k + 1..], next, edits - 1, try_candidate);
+        }
+    }
+}
+
+fn hrp_expand(hrp: &str) -> Vec<u8> {
+    let bytes = hrp.as_bytes();
+    bytes.iter().map(|b| b >> 5)
+        .chain(std::iter::once(0))
+        .chain(bytes.iter().map(|b| b & 31))
+        .collect()
+}
+
+fn polymod(values: impl Iterator<Item = u8>, start: u32) -> u32 {
+    let mut chk = start;
+    for value in values {
+        let top = chk >> 25;
+        chk = ((chk & 0x1ffffff) << 5) ^ value as u32;
+        for (i, generator) in BECH32_GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= generator;
+            }
+        }
+    }
+    chk
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALID: &str = "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq";
+
+    #[test]
+    fn test_single_substitution_located() {
+        // 'r' at position 9 mistyped as 'z'
+        let corrections = bech32_corrections("bc1qar0szrr7xfkvy5l643lydnw9re59gtzzwf5mdq");
+        assert_eq!(corrections, vec![Correction { address: VALID.to_string(), positions: vec![9] }]);
+    }
+
+    #[test]
+    fn test_two_substitutions_and_taproot() {
+        let taproot = "bc1p5d7rjq7g6rdk2yhzks9smlaqtedr4dekq08ge8ztwac72sfr9rusxg3297";
+        let corrections = bech32_corrections("bc1p5d7rjq7g6rdk2yhzks9smlaqtedr4dekq08ge8ztwac72sfr9rusxg3qq7");
+        assert!(corrections.iter().any(|c| c.address == taproot && c.positions == vec![60, 61]));
+    }
+
+    #[test]
+    fn test_base58_substitution_and_transposition() {
+        let genesis = "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa";
+
+        let corrections = base58_corrections("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNb");
+        assert_eq!(corrections, vec![Correction { address: genesis.to_string(), positions: vec![34] }]);
+
+        let corrections = base58_corrections("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivNfa");
+        assert!(corrections.iter().any(|c| c.address == genesis && c.positions == vec![32, 33]));
+    }
+
+    #[test]
+    fn test_base58_confusables() {
+        // Both 'W' characters of a P2SH address typed in lowercase
+        let corrections = base58_corrections("3J98t1wpEZ73CNmQviecrnyiWrnqRhwNLy");
+        assert!(corrections.iter().any(|c| c.address == "3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLy"));
+
+        // 'o' typed as zero in two places
+        let corrections = base58_corrections("1BoatSLRHtKNngkdXEeobR76b53LETtpyT".replace('o', "0").as_str());
+        assert!(corrections.iter().any(|c| c.address == "1BoatSLRHtKNngkdXEeobR76b53LETtpyT"));
+    }
+
+    #[test]
+    fn test_invalid_character_and_case() {
+        // 'b' is not in the bech32 alphabet
+        let corrections = bech32_corrections("bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdb");
+        assert!(corrections.iter().any(|c| c.address == VALID));
+
+        let corrections = bech32_corrections("bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdQ");
+        assert_eq!(corrections[0].positions, vec![42]);
+    }
+
+    #[test]
+    fn test_implausible_lengths_not_searched() {
+        assert!(base58_corrections(&format!("1{}", "i".repeat(150))).is_empty());
+        assert!(base58_corrections("1A1zP1eP5QGefi2DMPT").is_empty());
+        assert!(bech32_corrections(&format!("bc1q{}", "q".repeat(90))).is_empty());
+    }
+}
@@ -0,0 +1,311 @@
+use anyhow::{Result, anyhow, bail};
+use bitcoin::blockdata::script::{Builder, Instruction, PushBytesBuf, ScriptBuf};
+use bitcoin::opcodes::{self, Opcode};
+use bitcoin::{Address, Network, PublicKey};
+
+use crate::converter::AddressKind;
+
+/// Largest script a P2SH input can reveal: it is pushed as one stack element
+pub const MAX_REDEEM_SCRIPT_SIZE: usize = 520;
+/// Largest witness script nodes relay (policy, not consensus)
+pub const MAX_STANDARD_WITNESS_SCRIPT_SIZE: usize = 3600;
+
+/// Why a scriptPubKey has no address
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NoAddress {
+    /// `OP_RETURN <data>`: provably unspendable, carries data
+    NullData { data: Vec<u8> },
+    /// `m <pubkey>... n OP_CHECKMULTISIG` with the keys in the output itself
+    BareMultisig { required: u8, keys: u8 },
+    /// `<pubkey> OP_CHECKSIG`, which predates addresses
+    P2pk { public_key: PublicKey },
+    /// Matches no standard output template
+    NonStandard,
+}
+
+impl NoAddress {
+    /// Short machine-readable name
+    pub fn kind(&self) -> &'static str {
+        match self {
+            NoAddress::NullData { .. } => "null_data",
+            NoAddress::BareMultisig { .. } => "bare_multisig",
+            NoAddress::P2pk { .. } => "p2pk",
+            NoAddress::NonStandard => "non_standard",
+        }
+    }
+
+    pub fn explanation(&self) -> String {
+        match self {
+            NoAddress::NullData { data } => format!(
+                "OP_RETURN output carrying {} bytes of data: it is provably unspendable, so nothing can be paid to it",
+                data.len(),
+            ),
+            NoAddress::BareMultisig { required, keys } => format!(
+                "Bare {}-of-{} multisig: the keys sit in the output itself and no address format encodes them; \
+                 wrap the script in P2SH or P2WSH to get an address",
+                required, keys,
+            ),
+            NoAddress::P2pk { .. } =>
+                "Pay-to-public-key: the key sits in the output itself and predates addresses; \
+                 the key's P2PKH address pays the same key but is a different script".to_string(),
+            NoAddress::NonStandard =>
+                "Matches no standard output template; if this is a redeem or witness script, \
+                 its P2SH and P2WSH addresses can be built from it".to_string(),
+        }
+    }
+}
+
+/// A scriptPubKey and the address it pays, if any
+#[derive(Debug, Clone)]
+pub struct ScriptPubKeyReport {
+    pub script: ScriptBuf,
+    pub network: Network,
+    pub address: Result<Address, NoAddress>,
+}
+
+/// Addresses that pay to a redeem or witness script
+#[derive(Debug, Clone)]
+pub struct RedeemScriptReport {
+    pub script: ScriptBuf,
+    pub network: Network,
+    /// P2SH (if the script fits), P2SH-P2WSH and P2WSH, in that order
+    pub addresses: Vec<(AddressKind, Address)>,
+    /// Reasons some of the addresses may be unspendable or unrelayable
+    pub warnings: Vec<String>,
+}
+
+/// Parse a script given in hex or ASM
+///
+/// ASM accepts both Bitcoin Core's form (`2 <hex> <hex> 2 OP_CHECKMULTISIG`,
+/// small numbers in decimal) and rust-bitcoin's (`OP_PUSHNUM_2
+/// OP_PUSHBYTES_33 <hex> ...`); the `OP_` prefix and case are optional.
+/// Explicit `OP_PUSHDATA1/2/4` pushes are kept byte for byte, even when a
+/// shorter push exists, so printed ASM parses back to the same script.
+pub fn parse_script(input: &str) -> Result<ScriptBuf> {
+    let input = input.trim();
+    if input.is_empty() {
+        bail!("Script is empty");
+    }
+    if !input.contains(char::is_whitespace) {
+        if let Ok(bytes) = hex::decode(input) {
+            return Ok(ScriptBuf::from_bytes(bytes));
+        }
+    }
+    parse_asm(input)
+}
+
+fn parse_asm(asm: &str) -> Result<ScriptBuf> {
+    let mut script = Vec::new();
+    let mut tokens = asm.split_whitespace();
+    while let Some(token) = tokens.next() {
+        let upper = token.to_ascii_uppercase();
+        let name = upper.strip_prefix("OP_").unwrap_or(&upper);
+
+        // An explicit push opcode is followed by the data it pushes, and is
+        // kept as written: a non-minimal push changes the script's hash
+        if name.starts_with("PUSHBYTES_") || name.starts_with("PUSHDATA") {
+            let data = tokens.next().ok_or_else(|| anyhow!("{} must be followed by the data it pushes", token))?;
+            let bytes = hex::decode(data).map_err(|e| anyhow!("Invalid push data '{}' after {}: {}", data, token, e))?;
+            let too_long = || anyhow!("{} cannot push {} bytes", token, bytes.len());
+            match name {
+                "PUSHDATA1" => {
+                    script.push(opcodes::all::OP_PUSHDATA1.to_u8());
+                    script.push(u8::try_from(bytes.len()).map_err(|_| too_long())?);
+                }
+                "PUSHDATA2" => {
+                    script.push(opcodes::all::OP_PUSHDATA2.to_u8());
+                    script.extend(u16::try_from(bytes.len()).map_err(|_| too_long())?.to_le_bytes());
+                }
+                "PUSHDATA4" => {
+                    script.push(opcodes::all::OP_PUSHDATA4.to_u8());
+                    script.extend(u32::try_from(bytes.len()).map_err(|_| too_long())?.to_le_bytes());
+                }
+                _ => match name.strip_prefix("PUSHBYTES_").and_then(|length| length.parse::<u8>().ok()) {
+                    Some(length) if length as usize == bytes.len() && length < opcodes::all::OP_PUSHDATA1.to_u8() =>
+                        script.push(length),
+                    _ => bail!("{} is followed by {} bytes", token, bytes.len()),
+                },
+            }
+            script.extend(bytes);
+            continue;
+        }
+
+        let builder = Builder::new();
+        let builder = if let Ok(number) = token.parse::<i64>() {
+            builder.push_int(number)
+        } else if let Some(number) = small_number(name) {
+            builder.push_int(number)
+        } else if let Some(opcode) = opcode_by_name(name) {
+            builder.push_opcode(opcode)
+        } else if let Ok(bytes) = hex::decode(token) {
+            builder.push_slice(push_bytes(bytes)?)
+        } else {
+            bail!("'{}' is neither an opcode nor hex data", token);
+        };
+        script.extend(builder.into_bytes());
+    }
+    Ok(ScriptBuf::from_bytes(script))
+}
+
+fn push_bytes(bytes: Vec<u8>) -> Result<PushBytesBuf> {
+    let length = bytes.len();
+    PushBytesBuf::try_from(bytes).map_err(|_| anyhow!("Cannot push {} bytes in one element", length))
+}
+
+/// Bitcoin Core's names for the number-pushing opcodes
+fn small_number(name: &str) -> Option<i64> {
+    match name {
+        "FALSE" => Some(0),
+        "TRUE" => Some(1),
+        "1NEGATE" | "PUSHNUM_NEG1" => Some(-1),
+        _ => name.strip_prefix("PUSHNUM_").unwrap_or(name).parse().ok().filter(|n| (0..=16).contains(n)),
+    }
+}
+
+/// Non-push opcode by its name without `OP_`, accepting Core's aliases
+fn opcode_by_name(name: &str) -> Option<Opcode> {
+    match name {
+        "CHECKLOCKTIMEVERIFY" | "NOP2" => return Some(opcodes::all::OP_CLTV),
+        "CHECKSEQUENCEVERIFY" | "NOP3" => return Some(opcodes::all::OP_CSV),
+        _ => {}
+    }
+    (0x4f..=0xff)
+        .map(Opcode::from)
+        .find(|opcode| opcode.to_string().strip_prefix("OP_") == Some(name))
+}
+
+/// The address a scriptPubKey pays, or why it has none
+pub fn script_pubkey_address(input: &str, network: Network) -> Result<ScriptPubKeyReport> {
+    let script = parse_script(input)?;
+    let address = Address::from_script(&script, network).map_err(|_| no_address_reason(&script));
+    Ok(ScriptPubKeyReport { script, network, address })
+}
+
+fn no_address_reason(script: &ScriptBuf) -> NoAddress {
+    if script.is_op_return() {
+        let data = script.instructions().skip(1)
+            .filter_map(|instruction| match instruction {
+                Ok(Instruction::PushBytes(bytes)) => Some(bytes.as_bytes().to_vec()),
+                _ => None,
+            })
+            .flatten()
+            .collect();
+        return NoAddress::NullData { data };
+    }
+    if let Some(public_key) = script.p2pk_public_key() {
+        return NoAddress::P2pk { public_key };
+    }
+    if script.is_multisig() {
+        let ops: Vec<_> = script.instructions().filter_map(Result::ok).collect();
+        let pushnum = |instruction: Option<&Instruction>| match instruction {
+            Some(Instruction::Op(op)) if (0x51..=0x60).contains(&op.to_u8()) => Some(op.to_u8() - 0x50),
+            _ => None,
+        };
+        if let (Some(required), Some(keys)) = (pushnum(ops.first()), pushnum(ops.iter().rev().nth(1))) {
+            return NoAddress::BareMultisig { required, keys };
+        }
+    }
+    NoAddress::NonStandard
+}
+
+/// The P2SH, P2SH-P2WSH and P2WSH addresses of a redeem or witness script
+pub fn redeem_script_addresses(input: &str, network: Network) -> Result<RedeemScriptReport> {
+    let script = parse_script(input)?;
+    let mut addresses = Vec::new();
+    let mut warnings = Vec::new();
+
+    if script.len() <= MAX_REDEEM_SCRIPT_SIZE {
+        addresses.push((AddressKind::P2sh, Address::p2sh(&script, network)?));
+    } else {
+        warnings.push(format!(
+            "The script is {} bytes; P2SH can only reveal {}, so it has no P2SH address",
+            script.len(), MAX_REDEEM_SCRIPT_SIZE,
+        ));
+    }
+    addresses.push((AddressKind::P2shP2wsh, Address::p2shwsh(&script, network)));
+    addresses.push((AddressKind::P2wsh, Address::p2wsh(&script, network)));
+
+    if script.len() > MAX_STANDARD_WITNESS_SCRIPT_SIZE {
+        warnings.push(format!(
+            "The script is {} bytes; witness scripts over {} bytes are not relayed, so the SegWit addresses \
+             are hard to spend from",
+            script.len(), MAX_STANDARD_WITNESS_SCRIPT_SIZE,
+        ));
+    }
+    let uncompressed = script.instructions().any(|instruction| matches!(
+        instruction,
+        Ok(Instruction::PushBytes(bytes)) if bytes.len() == 65 && bytes.as_bytes()[0] == 0x04
+    ));
+    if uncompressed {
+        warnings.push("The script contains an uncompressed public key; SegWit only relays spends with \
+                       compressed keys, so use the P2SH address".to_string());
+    }
+
+    Ok(RedeemScriptReport { script, network, addresses, warnings })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2-of-2 of the public keys of secret keys 2 and 3
+    const MULTISIG_ASM: &str = "2 02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5 \
+        02f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9 2 OP_CHECKMULTISIG";
+
+    #[test]
+    fn test_parse_script() {
+        let script = parse_script(MULTISIG_ASM).unwrap();
+        assert!(script.is_multisig());
+        assert_eq!(parse_script(&script.to_hex_string()).unwrap(), script);
+        // rust-bitcoin's own ASM round-trips
+        assert_eq!(parse_script(&script.to_asm_string()).unwrap(), script);
+
+        let csv = parse_script("144 op_checksequenceverify OP_DROP").unwrap();
+        assert_eq!(csv.to_hex_string(), "029000b275");
+
+        // Non-minimal pushes keep their encoding, so the ASM printed back
+        // gives the same script and addresses
+        for hex in ["4c01ab75", "4d0100ab75", "4e01000000ab75"] {
+            let script = parse_script(hex).unwrap();
+            assert_eq!(parse_script(&script.to_asm_string()).unwrap(), script);
+            assert_eq!(
+                redeem_script_addresses(&script.to_asm_string(), Network::Bitcoin).unwrap().addresses[0].1,
+                redeem_script_addresses(hex, Network::Bitcoin).unwrap().addresses[0].1,
+            );
+        }
+
+        assert!(parse_script("OP_NOTANOPCODE").is_err());
+        assert!(parse_script("OP_PUSHBYTES_2 00").is_err());
+    }
+
+    #[test]
+    fn test_script_pubkey_address() {
+        let report = script_pubkey_address("0014751e76e8199196d454941c45d1b3a323f1433bd6", Network::Bitcoin).unwrap();
+        assert_eq!(report.address.unwrap().to_string(), "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+
+        let report = script_pubkey_address("OP_RETURN 68656c6c6f", Network::Bitcoin).unwrap();
+        assert_eq!(report.address.unwrap_err(), NoAddress::NullData { data: b"hello".to_vec() });
+
+        let report = script_pubkey_address(MULTISIG_ASM, Network::Bitcoin).unwrap();
+        assert_eq!(report.address.unwrap_err(), NoAddress::BareMultisig { required: 2, keys: 2 });
+
+        let report = script_pubkey_address("OP_DUP OP_DROP", Network::Bitcoin).unwrap();
+        assert_eq!(report.address.unwrap_err(), NoAddress::NonStandard);
+    }
+
+    #[test]
+    fn test_redeem_script_addresses() {
+        // BIP173: P2WSH of "<key> OP_CHECKSIG"
+        let script = "210279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798ac";
+        let report = redeem_script_addresses(script, Network::Bitcoin).unwrap();
+        let kinds: Vec<_> = report.addresses.iter().map(|(kind, _)| *kind).collect();
+        assert_eq!(kinds, [AddressKind::P2sh, AddressKind::P2shP2wsh, AddressKind::P2wsh]);
+        assert_eq!(report.addresses[2].1.to_string(), "bc1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3qccfmv3");
+        assert!(report.warnings.is_empty());
+
+        let long = format!("OP_PUSHDATA2 {} OP_DROP OP_TRUE", "00".repeat(MAX_REDEEM_SCRIPT_SIZE));
+        let report = redeem_script_addresses(&long, Network::Testnet).unwrap();
+        assert_eq!(report.addresses.len(), 2);
+        assert_eq!(report.warnings.len(), 1);
+    }
+}
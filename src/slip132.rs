@@ -0,0 +1,88 @@
+use anyhow::{Result, anyhow};
+use bitcoin::base58;
+use bitcoin::bip32::Xpub;
+use bitcoin::Network;
+
+use crate::hd_wallet::ScriptType;
+
+/// SLIP-132 extended public key prefixes and their version bytes
+///
+/// `xpub`/`tpub` do not pin a script type: BIP44 (P2PKH) and BIP86 (P2TR)
+/// accounts both use them.
+const VERSIONS: [(&str, [u8; 4], bool, Option<ScriptType>); 6] = [
+    ("xpub", [0x04, 0x88, 0xb2, 0x1e], true, None),
+    ("ypub", [0x04, 0x9d, 0x7c, 0xb2], true, Some(ScriptType::P2shP2wpkh)),
+    ("zpub", [0x04, 0xb2, 0x47, 0x46], true, Some(ScriptType::P2wpkh)),
+    ("tpub", [0x04, 0x35, 0x87, 0xcf], false, None),
+    ("upub", [0x04, 0x4a, 0x52, 0x62], false, Some(ScriptType::P2shP2wpkh)),
+    ("vpub", [0x04, 0x5f, 0x1c, 0xf6], false, Some(ScriptType::P2wpkh)),
+];
+
+/// Encode an extended public key with the SLIP-132 prefix for `script_type`
+///
+/// P2SH-P2WPKH becomes `ypub`/`upub` and P2WPKH `zpub`/`vpub`; P2PKH and
+/// P2TR keep the plain `xpub`/`tpub` encoding.
+pub fn encode(xpub: &Xpub, script_type: ScriptType) -> String {
+    let mainnet = xpub.network == Network::Bitcoin;
+    let implied = Some(script_type).filter(|t| matches!(t, ScriptType::P2shP2wpkh | ScriptType::P2wpkh));
+    let (_, version, _, _) = VERSIONS.iter()
+        .find(|(_, _, is_mainnet, kind)| *is_mainnet == mainnet && *kind == implied)
+        .expect("every network has a prefix for each script type");
+
+    let mut data = xpub.encode();
+    data[..4].copy_from_slice(version);
+    base58::encode_check(&data)
+}
+
+/// Decode any SLIP-132 extended public key (`xpub`, `ypub`, `zpub`, `tpub`,
+/// `upub`, `vpub`)
+///
+/// Returns the key and the script type its prefix implies, if any. Test
+/// network keys decode as testnet.
+pub fn decode(encoded: &str) -> Result<(Xpub, Option<ScriptType>)> {
+    let mut data = base58::decode_check(encoded.trim())
+        .map_err(|e| anyhow!("Invalid extended public key: {}", e))?;
+    if data.len() != 78 {
+        return Err(anyhow!("Invalid extended public key: expected 78 bytes, found {}", data.len()));
+    }
+
+    let (_, _, mainnet, script_type) = VERSIONS.iter()
+        .find(|(_, version, _, _)| data[..4] == version[..])
+        .ok_or_else(|| anyhow!("Unsupported extended key version {} (private keys are not accepted)",
+                               hex::encode(&data[..4])))?;
+
+    // Swap in the plain xpub/tpub version so rust-bitcoin can parse it
+    let plain = if *mainnet { VERSIONS[0].1 } else { VERSIONS[3].1 };
+    data[..4].copy_from_slice(&plain);
+    let xpub = Xpub::decode(&data).map_err(|e| anyhow!("Invalid extended public key: {}", e))?;
+    Ok((xpub, *script_type))
+}
+
+/// The four-letter prefix of an encoded extended key, e.g. `zpub`
+pub fn prefix(encoded: &str) -> &str {
+    encoded.get(..4).unwrap_or(encoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // BIP84 account key for "abandon ... about" at m/84'/0'/0'
+    const ZPUB: &str = "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs";
+
+    #[test]
+    fn test_round_trip() {
+        let (xpub, script_type) = decode(ZPUB).unwrap();
+        assert_eq!(script_type, Some(ScriptType::P2wpkh));
+        assert!(xpub.to_string().starts_with("xpub"));
+        assert_eq!(encode(&xpub, ScriptType::P2wpkh), ZPUB);
+        assert_eq!(encode(&xpub, ScriptType::P2tr), xpub.to_string());
+        assert!(encode(&xpub, ScriptType::P2shP2wpkh).starts_with("ypub"));
+    }
+
+    #[test]
+    fn test_rejects_private_keys() {
+        let xprv = "xprv9s21ZrQH143K3GJpoapnV8SFfukcVBSfeCficPSGfubmSFDxo1kuHnLisriDvSnRRuL2Qrg5ggqHKNVpxR86QEC8w35uxmGoggxtQTPvfUu";
+        assert!(decode(xprv).is_err());
+    }
+}
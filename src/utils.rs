@@ -1,5 +1,22 @@
-#[cfg(test)]
-mod tests {
-    use super::*;
-    
+//! Helper functions shared across modules
+
+use bitcoin::Network;
+
+/// Every network an address or key can belong to, mainnet first
+pub const NETWORKS: [Network; 4] = [Network::Bitcoin, Network::Testnet, Network::Signet, Network::Regtest];
+
+/// Lowercase user-facing network name (`mainnet` rather than `bitcoin`)
+pub fn network_name(network: Network) -> &'static str {
+    match network {
+        Network::Bitcoin => "mainnet",
+        Network::Testnet => "testnet",
+        Network::Signet => "signet",
+        Network::Regtest => "regtest",
+        _ => "unknown",
+    }
+}
+
+/// Join network names with " / " for display, e.g. "testnet / signet"
+pub fn join_network_names(networks: &[Network]) -> String {
+    networks.iter().map(|n| network_name(*n)).collect::<Vec<_>>().join(" / ")
 }
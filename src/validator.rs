@@ -1,148 +1,307 @@
-use bitcoin::{Address, Network};
-use colored::*;
-use anyhow::Result;
+use bitcoin::address::{NetworkUnchecked, ParseError};
+use bitcoin::bech32::primitives::decode::{CharError, SegwitHrpstringError, UncheckedHrpstringError};
+use bitcoin::{base58, Address, AddressType, Network, ScriptBuf};
+use std::collections::BTreeMap;
+use std::io::{self, BufRead};
 use std::str::FromStr;
+use thiserror::Error;
 
-/// Validate a Bitcoin address and display detailed information
-pub fn validate_address(address_str: &str) -> Result<()> {
-    println!("\n{}", "━".repeat(70).bright_blue());
-    println!("{}", "  🔍 VALIDATING BITCOIN ADDRESS".bright_cyan().bold());
-    println!("{}", "━".repeat(70).bright_blue());
-    
-    // Try to parse the address
-    match Address::from_str(address_str) {
-        Ok(address) => {
-            // Need to check the network
-            let address_checked = address.assume_checked();
-            display_valid_address(&address_checked, address_str);
-        }
-        Err(e) => {
-            display_invalid_address(address_str, &e);
+use crate::recovery::{self, Correction};
+use crate::utils::{self, NETWORKS};
+
+/// Reasons an address fails validation
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ValidationError {
+    /// A character outside the Base58 or Bech32 alphabet (1-based position)
+    #[error("invalid character '{character}' at position {position}")]
+    InvalidCharacter { character: char, position: usize },
+    /// Bech32 addresses must be entirely lowercase or entirely uppercase
+    #[error("mixed upper and lower case is not allowed in bech32 addresses")]
+    MixedCase,
+    /// Every character is valid but the checksum does not match
+    #[error("checksum mismatch - the address most likely contains a typo")]
+    BadChecksum,
+    /// The decoded payload or witness program has the wrong size
+    #[error("wrong length: {0}")]
+    WrongLength(String),
+    /// A witness version other than v0 (SegWit) or v1 (Taproot); such
+    /// outputs are anyone-can-spend until a soft fork defines them
+    #[error("unknown witness version {0} (only v0 SegWit and v1 Taproot are defined)")]
+    UnknownWitnessVersion(u8),
+    /// The address is well-formed but encodes a different network
+    #[error("address belongs to {} but {} was required", utils::join_network_names(.found), utils::network_name(*.required))]
+    WrongNetwork {
+        required: Network,
+        /// Every network the address encoding is valid for
+        found: Vec<Network>,
+    },
+    /// Anything else that prevents the string from parsing as an address
+    #[error("{0}")]
+    Malformed(String),
+}
+
+impl ValidationError {
+    /// Stable snake_case identifier of the failure category
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ValidationError::InvalidCharacter { .. } => "invalid_character",
+            ValidationError::MixedCase => "mixed_case",
+            ValidationError::BadChecksum => "bad_checksum",
+            ValidationError::WrongLength(_) => "wrong_length",
+            ValidationError::UnknownWitnessVersion(_) => "unknown_witness_version",
+            ValidationError::WrongNetwork { .. } => "wrong_network",
+            ValidationError::Malformed(_) => "malformed",
         }
     }
-    
-    Ok(())
-}
-
-fn display_valid_address(address: &Address, address_str: &str) {
-    println!("\n{}", "✅ VALID ADDRESS".bright_green().bold());
-    println!();
-    
-    // Determine address type
-    let (address_type, description) = get_address_type(address);
-    
-    // Display address information
-    println!("  {} {}", "Address:".bold(), address_str.bright_white());
-    println!("  {} {}", "Type:".bold(), address_type.bright_yellow());
-    println!("  {} {}", "Description:".bold(), description.white());
-    println!("  {} {}", "Network:".bold(), get_network_display(*address.network()));
-    
-    // Display technical details
-    println!("\n{}", "  📋 Technical Details:".bright_cyan().bold());
-    
-    // Show script type
-    let script_pubkey = address.script_pubkey();
-    println!("  {} {}", "Script Type:".bold(), get_script_type_display(&script_pubkey));
-    println!("  {} {} bytes", "Script Length:".bold(), script_pubkey.len());
-    println!("  {} {}", "Script (hex):".bold(), script_pubkey.to_hex_string().dimmed());
-    
-    // Address characteristics
-    println!("\n{}", "  💡 Characteristics:".bright_cyan().bold());
-    
-    match address.address_type() {
-        Some(bitcoin::AddressType::P2pkh) => {
-            println!("  {} Legacy format (most compatible)", "•".yellow());
-            println!("  {} Higher transaction fees", "•".yellow());
-            println!("  {} Starts with '1'", "•".yellow());
-            println!("  {} Base58Check encoding", "•".yellow());
-        }
-        Some(bitcoin::AddressType::P2sh) => {
-            println!("  {} Script Hash format", "•".yellow());
-            println!("  {} Can contain any script", "•".yellow());
-            println!("  {} Starts with '3'", "•".yellow());
-            println!("  {} Often used for multisig or SegWit", "•".yellow());
-        }
-        Some(bitcoin::AddressType::P2wpkh) => {
-            println!("  {} Native SegWit (recommended)", "•".green());
-            println!("  {} ~40% lower transaction fees", "•".green());
-            println!("  {} Starts with 'bc1q'", "•".green());
-            println!("  {} Bech32 encoding", "•".green());
-        }
-        Some(bitcoin::AddressType::P2wsh) => {
-            println!("  {} Native SegWit Script", "•".green());
-            println!("  {} Lower fees than legacy", "•".green());
-            println!("  {} Starts with 'bc1q'", "•".green());
-            println!("  {} For complex scripts", "•".green());
-        }
-        Some(bitcoin::AddressType::P2tr) => {
-            println!("  {} Taproot (most advanced)", "•".bright_green());
-            println!("  {} Enhanced privacy", "•".bright_green());
-            println!("  {} More efficient multisig", "•".bright_green());
-            println!("  {} Starts with 'bc1p'", "•".bright_green());
-            println!("  {} Bech32m encoding", "•".bright_green());
-        }
-        None => {
-            println!("  {} Unknown address type", "•".red());
-        }
-        Some(_) => {
-            println!("  {} Other address type", "•".yellow());
-        }
+}
+
+/// Everything learned about a successfully parsed Bitcoin address
+#[derive(Debug, Clone)]
+pub struct AddressInfo {
+    /// The address exactly as it was supplied
+    pub input: String,
+    /// Parsed address
+    pub address: Address,
+    /// Detected address type (`None` for non-standard witness programs)
+    pub address_type: Option<AddressType>,
+    /// Network the address was checked against, or the one it encodes
+    pub network: Network,
+    /// Every network sharing this address encoding; testnet and signet
+    /// (and regtest for Base58) cannot be told apart from the address alone
+    pub possible_networks: Vec<Network>,
+    /// Locking script the address pays to
+    pub script_pubkey: ScriptBuf,
+}
+
+impl AddressInfo {
+    /// Short human-readable name of the address type
+    pub fn type_name(&self) -> &'static str {
+        address_type_details(self.address_type).0
+    }
+
+    /// One-line description of the address type
+    pub fn description(&self) -> &'static str {
+        address_type_details(self.address_type).1
+    }
+
+    /// Name of the script template behind the address
+    pub fn script_type(&self) -> &'static str {
+        script_type_name(&self.script_pubkey)
+    }
+
+    /// Whether the address is valid on more than one network
+    pub fn is_network_ambiguous(&self) -> bool {
+        self.possible_networks.len() > 1
     }
-    
-    // Compatibility notes
-    println!("\n{}", "  🔌 Compatibility:".bright_cyan().bold());
-    match address.address_type() {
-        Some(bitcoin::AddressType::P2pkh) | Some(bitcoin::AddressType::P2sh) => {
-            println!("  {} Compatible with all wallets", "✓".green());
+}
+
+/// Validate a Bitcoin address and collect detailed information about it
+///
+/// When `network` is given the address must be valid on it, otherwise
+/// [`ValidationError::WrongNetwork`] is returned.
+pub fn validate_address(address_str: &str, network: Option<Network>) -> Result<AddressInfo, ValidationError> {
+    let unchecked = Address::from_str(address_str)
+        .map_err(|e| classify_parse_error(address_str, e))?;
+
+    if let Some(version) = unchecked.assume_checked_ref().script_pubkey().witness_version() {
+        if version.to_num() > 1 {
+            return Err(ValidationError::UnknownWitnessVersion(version.to_num()));
         }
-        Some(bitcoin::AddressType::P2wpkh) | Some(bitcoin::AddressType::P2wsh) => {
-            println!("  {} Most modern wallets (post-2017)", "✓".green());
-            println!("  {} May not work with very old software", "!".yellow());
+    }
+
+    let possible_networks = possible_networks(&unchecked);
+
+    let address = match network {
+        Some(required) => unchecked.require_network(required)
+            .map_err(|_| ValidationError::WrongNetwork { required, found: possible_networks.clone() })?,
+        None => unchecked.assume_checked(),
+    };
+
+    Ok(AddressInfo {
+        input: address_str.to_string(),
+        address_type: address.address_type(),
+        network: network.unwrap_or(*address.network()),
+        possible_networks,
+        script_pubkey: address.script_pubkey(),
+        address,
+    })
+}
+
+/// Result of validating one line of a batch
+#[derive(Debug, Clone)]
+pub struct BatchEntry {
+    /// 1-based line number in the input
+    pub line: usize,
+    /// Trimmed line contents
+    pub input: String,
+    pub result: Result<AddressInfo, ValidationError>,
+}
+
+/// Validate one address per line, skipping blank lines and `#` comments
+///
+/// Lines are read lazily so arbitrarily large inputs can be streamed.
+pub fn validate_lines<R: BufRead>(
+    reader: R,
+    network: Option<Network>,
+) -> impl Iterator<Item = io::Result<BatchEntry>> {
+    reader.lines()
+        .enumerate()
+        .filter_map(move |(i, line)| match line {
+            Err(e) => Some(Err(e)),
+            Ok(line) => {
+                let input = line.trim();
+                if input.is_empty() || input.starts_with('#') {
+                    return None;
+                }
+                Some(Ok(BatchEntry {
+                    line: i + 1,
+                    input: input.to_string(),
+                    result: validate_address(input, network),
+                }))
+            }
+        })
+}
+
+/// Running totals for a batch validation
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BatchSummary {
+    pub total: usize,
+    pub valid: usize,
+    pub invalid: usize,
+    /// Valid addresses by type (`p2wpkh`, ..., or `unknown`)
+    pub by_type: BTreeMap<String, usize>,
+    /// Valid addresses by network; ambiguous encodings are grouped
+    /// together, e.g. `testnet / signet`, unless a network was required
+    pub by_network: BTreeMap<String, usize>,
+    /// Invalid addresses by [`ValidationError::kind`]
+    pub by_error: BTreeMap<&'static str, usize>,
+    /// Network the batch was validated against, which settles ambiguity
+    pub required_network: Option<Network>,
+}
+
+impl BatchSummary {
+    /// Totals for a batch validated against `network`, if any
+    pub fn new(network: Option<Network>) -> Self {
+        BatchSummary { required_network: network, ..Default::default() }
+    }
+
+    /// Add one validation result to the totals
+    pub fn record(&mut self, result: &Result<AddressInfo, ValidationError>) {
+        self.total += 1;
+        match result {
+            Ok(info) => {
+                self.valid += 1;
+                let address_type = info.address_type.map_or("unknown".to_string(), |t| t.to_string());
+                *self.by_type.entry(address_type).or_default() += 1;
+                let network = if self.required_network.is_none() && info.is_network_ambiguous() {
+                    utils::join_network_names(&info.possible_networks)
+                } else {
+                    utils::network_name(info.network).to_string()
+                };
+                *self.by_network.entry(network).or_default() += 1;
+            }
+            Err(e) => {
+                self.invalid += 1;
+                *self.by_error.entry(e.kind()).or_default() += 1;
+            }
         }
-        Some(bitcoin::AddressType::P2tr) => {
-            println!("  {} Newest wallets (post-2021)", "✓".green());
-            println!("  {} Not supported by older wallets", "!".yellow());
+    }
+}
+
+/// Suggest corrected addresses for an input that failed validation
+///
+/// Only a bad checksum, an invalid character or mixed case is a likely
+/// typo; other errors, and inputs no address could have the length of,
+/// return an empty list.
+pub fn suggest_corrections(address_str: &str, error: &ValidationError) -> Vec<Correction> {
+    match error {
+        ValidationError::BadChecksum | ValidationError::InvalidCharacter { .. } | ValidationError::MixedCase => {
+            if is_bech32_like(address_str) {
+                recovery::bech32_corrections(address_str)
+            } else {
+                recovery::base58_corrections(address_str)
+            }
         }
-        None => {}
-        Some(_) => {}
-    }
-    
-    println!();
-}
-
-fn display_invalid_address(address_str: &str, error: &bitcoin::address::ParseError) {
-    println!("\n{}", "❌ INVALID ADDRESS".bright_red().bold());
-    println!();
-    println!("  {} {}", "Address:".bold(), address_str.bright_white());
-    println!("  {} {}", "Error:".bold(), format!("{:?}", error).red());
-    
-    // Provide helpful hints
-    println!("\n{}", "  💡 Common Issues:".bright_yellow().bold());
-    println!("  {} Check for typos or missing characters", "•".yellow());
-    println!("  {} Valid formats:", "•".yellow());
-    println!("      {} 1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa", "-".dimmed());
-    println!("      {} 3J98t1WpEZ73CNmYviecrnyiWrnqRhWNLy", "-".dimmed());
-    println!("      {} bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq", "-".dimmed());
-    println!("      {} bc1p5d7rjq7g6rdk2yhzks9smlaqtedr4dekq08ge8ztwac72sfr9rusxg3297", "-".dimmed());
-    println!("  {} Testnet addresses start with 'm', 'n', '2', 'tb1'", "•".yellow());
-    println!();
-}
-
-fn get_address_type(address: &Address) -> (&str, &str) {
-    match address.address_type() {
-        Some(bitcoin::AddressType::P2pkh) => {
+        _ => Vec::new(),
+    }
+}
+
+fn is_bech32_like(address_str: &str) -> bool {
+    let lower = address_str.to_lowercase();
+    ["bc1", "tb1", "bcrt1"].iter().any(|prefix| lower.starts_with(prefix))
+}
+
+/// Decoded size of a Base58Check address: version byte, hash160, checksum
+const BASE58_PAYLOAD_LEN: usize = 25;
+
+/// Map a rust-bitcoin parse error onto a validation failure category
+fn classify_parse_error(address_str: &str, error: ParseError) -> ValidationError {
+    // Bech32 only restricts characters after the separator, so search there
+    let invalid_character = |character: char, skip: usize| ValidationError::InvalidCharacter {
+        character,
+        position: address_str.chars().skip(skip).position(|c| c == character).map_or(0, |i| skip + i + 1),
+    };
+
+    match error {
+        ParseError::Base58(e) => match e {
+            base58::Error::BadByte(byte) => invalid_character(byte as char, 0),
+            // A checksum over the wrong payload size: the string was truncated or padded
+            base58::Error::BadChecksum(..) => match base58::decode(address_str) {
+                Ok(payload) if payload.len() != BASE58_PAYLOAD_LEN => ValidationError::WrongLength(format!(
+                    "decodes to {} bytes, a Base58 address has {} (version, 20-byte hash, checksum)",
+                    payload.len(), BASE58_PAYLOAD_LEN,
+                )),
+                _ => ValidationError::BadChecksum,
+            },
+            base58::Error::InvalidLength(_) | base58::Error::TooShort(_) => {
+                ValidationError::WrongLength(format!("{} characters is not a valid Base58 address", address_str.len()))
+            }
+            base58::Error::InvalidAddressVersion(version) => {
+                ValidationError::Malformed(format!("unknown address version byte {:#04x}", version))
+            }
+            other => ValidationError::Malformed(other.to_string()),
+        },
+        ParseError::Bech32(e) => match e.0 {
+            SegwitHrpstringError::Unchecked(UncheckedHrpstringError::Char(e)) => match e {
+                CharError::MixedCase => ValidationError::MixedCase,
+                CharError::InvalidChar(c) => invalid_character(c, address_str.rfind('1').map_or(0, |sep| sep + 1)),
+                CharError::InvalidChecksum => ValidationError::BadChecksum,
+                CharError::InvalidChecksumLength => ValidationError::WrongLength(e.to_string()),
+                other => ValidationError::Malformed(other.to_string()),
+            },
+            SegwitHrpstringError::Checksum(_) => ValidationError::BadChecksum,
+            SegwitHrpstringError::WitnessLength(e) => ValidationError::WrongLength(e.to_string()),
+            SegwitHrpstringError::InvalidWitnessVersion(v) => ValidationError::UnknownWitnessVersion(v.to_u8()),
+            other => ValidationError::Malformed(other.to_string()),
+        },
+        ParseError::WitnessProgram(e) => ValidationError::WrongLength(e.to_string()),
+        other => ValidationError::Malformed(other.to_string()),
+    }
+}
+
+/// Every network an unchecked address is valid for
+pub fn possible_networks(address: &Address<NetworkUnchecked>) -> Vec<Network> {
+    NETWORKS.into_iter()
+        .filter(|network| address.is_valid_for_network(*network))
+        .collect()
+}
+
+/// Name and description for an address type
+pub fn address_type_details(address_type: Option<AddressType>) -> (&'static str, &'static str) {
+    match address_type {
+        Some(AddressType::P2pkh) => {
             ("P2PKH (Pay-to-PubKey-Hash)", "Legacy address format, most compatible")
         }
-        Some(bitcoin::AddressType::P2sh) => {
+        Some(AddressType::P2sh) => {
             ("P2SH (Pay-to-Script-Hash)", "Can contain any script, often used for multisig")
         }
-        Some(bitcoin::AddressType::P2wpkh) => {
+        Some(AddressType::P2wpkh) => {
             ("P2WPKH (Pay-to-Witness-PubKey-Hash)", "Native SegWit, lower fees")
         }
-        Some(bitcoin::AddressType::P2wsh) => {
+        Some(AddressType::P2wsh) => {
             ("P2WSH (Pay-to-Witness-Script-Hash)", "Native SegWit for scripts")
         }
-        Some(bitcoin::AddressType::P2tr) => {
+        Some(AddressType::P2tr) => {
             ("P2TR (Pay-to-Taproot)", "Taproot, most advanced privacy and efficiency")
         }
         None => ("Unknown", "Address type could not be determined"),
@@ -150,51 +309,128 @@ fn get_address_type(address: &Address) -> (&str, &str) {
     }
 }
 
-fn get_network_display(network: Network) -> ColoredString {
-    match network {
-        Network::Bitcoin => "Mainnet (Production)".bright_green(),
-        Network::Testnet => "Testnet (Testing)".bright_yellow(),
-        Network::Signet => "Signet (Testing)".bright_yellow(),
-        Network::Regtest => "Regtest (Local)".bright_magenta(),
-        _ => "Unknown Network".red(),
-    }
-}
-
-fn get_script_type_display(script: &bitcoin::Script) -> ColoredString {
+/// Name of the standard script template a scriptPubKey matches
+pub fn script_type_name(script: &bitcoin::Script) -> &'static str {
     if script.is_p2pkh() {
-        "P2PKH".bright_yellow()
+        "P2PKH"
     } else if script.is_p2sh() {
-        "P2SH".bright_yellow()
+        "P2SH"
     } else if script.is_p2wpkh() {
-        "P2WPKH (SegWit)".bright_green()
+        "P2WPKH (SegWit)"
     } else if script.is_p2wsh() {
-        "P2WSH (SegWit)".bright_green()
+        "P2WSH (SegWit)"
     } else if script.is_p2tr() {
-        "P2TR (Taproot)".bright_cyan()
+        "P2TR (Taproot)"
     } else {
-        "Unknown".red()
+        "Unknown"
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    
+
     #[test]
     fn test_valid_legacy_address() {
-        let result = validate_address("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa");
-        assert!(result.is_ok());
+        let info = validate_address("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa", None).unwrap();
+        assert_eq!(info.address_type, Some(AddressType::P2pkh));
+        assert_eq!(info.network, Network::Bitcoin);
+        assert_eq!(info.script_type(), "P2PKH");
     }
-    
+
     #[test]
     fn test_valid_segwit_address() {
-        let result = validate_address("bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq");
-        assert!(result.is_ok());
+        let info = validate_address("bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq", None).unwrap();
+        assert_eq!(info.address_type, Some(AddressType::P2wpkh));
+        assert_eq!(info.script_pubkey.len(), 22);
     }
-    
+
     #[test]
     fn test_invalid_address() {
-        let result = validate_address("invalid_address_123");
-        assert!(result.is_ok()); // Function returns Ok but displays error
+        let result = validate_address("invalid_address_123", None);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_failure_categories() {
+        let kind = |address: &str| validate_address(address, None).unwrap_err().kind();
+
+        // Last character changed from 'a' to 'b'
+        assert_eq!(kind("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNb"), "bad_checksum");
+        assert_eq!(kind("bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdz"), "bad_checksum");
+        assert_eq!(kind("bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdQ"), "mixed_case");
+        // Dropping one character still decodes to 25 bytes; dropping two does not
+        assert_eq!(kind("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfN"), "bad_checksum");
+        assert_eq!(kind("1A1zP1eP5QGefi2DMPTfTL5SLmv7Divf"), "wrong_length");
+        assert_eq!(kind("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNaa"), "wrong_length");
+        // BIP350 test vector with witness version 2
+        assert_eq!(kind("bc1zw508d6qejxtdg4y5r3zarvaryvaxxpcs"), "unknown_witness_version");
+        assert_eq!(
+            validate_address("1A1zP1eP5QGefi20MPTfTL5SLmv7DivfNa", None).unwrap_err(),
+            ValidationError::InvalidCharacter { character: '0', position: 16 },
+        );
+        assert_eq!(
+            validate_address("bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdb", None).unwrap_err(),
+            ValidationError::InvalidCharacter { character: 'b', position: 42 },
+        );
+    }
+
+    #[test]
+    fn test_batch_validation() {
+        let input = "# payout list\n1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa\n\n  bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq  \nnot-an-address\ntb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7\n";
+        let mut summary = BatchSummary::default();
+        let entries: Vec<BatchEntry> = validate_lines(input.as_bytes(), None).map(Result::unwrap).collect();
+        for entry in &entries {
+            summary.record(&entry.result);
+        }
+
+        assert_eq!(entries.iter().map(|e| e.line).collect::<Vec<_>>(), vec![2, 4, 5, 6]);
+        assert_eq!((summary.total, summary.valid, summary.invalid), (4, 3, 1));
+        assert_eq!(summary.by_network["mainnet"], 2);
+        assert_eq!(summary.by_network["testnet / signet"], 1);
+        assert_eq!(summary.by_type["p2wsh"], 1);
+
+        // A required network settles the testnet/signet ambiguity
+        let mut summary = BatchSummary::new(Some(Network::Testnet));
+        for entry in validate_lines(input.as_bytes(), Some(Network::Testnet)) {
+            summary.record(&entry.unwrap().result);
+        }
+        assert_eq!((summary.valid, summary.invalid), (1, 3));
+        assert_eq!(summary.by_network.len(), 1);
+        assert_eq!(summary.by_network["testnet"], 1);
+    }
+
+    #[test]
+    fn test_no_suggestions_for_implausible_input() {
+        let long = format!("1{}", "i".repeat(150));
+        let error = validate_address(&long, None).unwrap_err();
+        assert!(suggest_corrections(&long, &error).is_empty());
+
+        let truncated = "1A1zP1eP5QGefi2DMPTfTL5SLmv7Divf";
+        let error = validate_address(truncated, None).unwrap_err();
+        assert_eq!(error.kind(), "wrong_length");
+        assert!(suggest_corrections(truncated, &error).is_empty());
+
+        let typo = "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNb";
+        assert!(!suggest_corrections(typo, &validate_address(typo, None).unwrap_err()).is_empty());
+    }
+
+    #[test]
+    fn test_network_mismatch() {
+        let result = validate_address("tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7", Some(Network::Bitcoin));
+        assert_eq!(result.unwrap_err(), ValidationError::WrongNetwork {
+            required: Network::Bitcoin,
+            found: vec![Network::Testnet, Network::Signet],
+        });
+    }
+
+    #[test]
+    fn test_testnet_signet_ambiguity() {
+        let address = "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7";
+        let info = validate_address(address, None).unwrap();
+        assert!(info.is_network_ambiguous());
+
+        let info = validate_address(address, Some(Network::Signet)).unwrap();
+        assert_eq!(info.network, Network::Signet);
     }
 }